@@ -1,52 +1,163 @@
-use std::time::Duration;
+use std::{sync::atomic::AtomicBool, time::Duration};
 
 use create3::{
+    batch::calc_addr_batch,
     generate_salt, generate_salt_multithread, generate_salt_prefix,
     generate_salt_prefix_multithread,
+    raw::generate_raw_salt,
+    search::{generate_salt_resumable, SearchState},
+    Address, Create3Variant, Salt, SearchLimits,
 };
-use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
 
 fn generate_salt_benchmark(c: &mut Criterion) {
     let prefix = black_box("0000");
-    let deployer = black_box(hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap());
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
 
     c.bench_function("single-threaded generate salt", |b| {
-        b.iter(|| generate_salt(&deployer, prefix))
+        b.iter(|| {
+            generate_salt(
+                &deployer,
+                prefix,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+        })
     });
 }
 
 fn generate_salt_multithread_benchmark(c: &mut Criterion) {
     let prefix = black_box("0000");
-    let deployer = black_box(hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap());
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
 
     c.bench_function("multi-threaded generate salt", |b| {
-        b.iter(|| generate_salt_multithread(&deployer, prefix, 6))
+        b.iter(|| {
+            generate_salt_multithread(
+                &deployer,
+                prefix,
+                6,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+        })
     });
 }
 
 fn generate_salt_prefix_benchmark(c: &mut Criterion) {
     let prefix = black_box("0000");
     let salt_prefix = black_box("my_prefix_");
-    let deployer = black_box(hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap());
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
 
     c.bench_function("single-threaded generate salt", |b| {
-        b.iter(|| generate_salt_prefix(&deployer, salt_prefix, prefix))
+        b.iter(|| {
+            generate_salt_prefix(
+                &deployer,
+                salt_prefix,
+                prefix,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+        })
     });
 }
 
 fn generate_salt_prefix_multithread_benchmark(c: &mut Criterion) {
     let prefix = black_box("0000");
     let salt_prefix = black_box("my_prefix_");
-    let deployer = black_box(hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap());
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
 
     c.bench_function("multi-threaded generate salt", |b| {
-        b.iter(|| generate_salt_prefix_multithread(&deployer, salt_prefix, prefix, 6))
+        b.iter(|| {
+            generate_salt_prefix_multithread(
+                &deployer,
+                salt_prefix,
+                prefix,
+                6,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+        })
+    });
+}
+
+fn generate_raw_salt_benchmark(c: &mut Criterion) {
+    let prefix = black_box("0000");
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
+
+    c.bench_function("single-threaded generate raw salt", |b| {
+        b.iter(|| {
+            generate_raw_salt(
+                &deployer,
+                &[],
+                prefix,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+        })
+    });
+}
+
+fn seeded_search_benchmark(c: &mut Criterion) {
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
+    let stop = AtomicBool::new(false);
+
+    // A fixed seed tests the same salts on every iteration, so the time per iteration measures throughput.
+    // Each salt costs three keccak permutations (salt string, proxy address and contract address), which bound
+    // the scalar hasher. Run with `--features simd` to measure hashing several salts at once.
+    let new_state = || {
+        SearchState::new(
+            &deployer,
+            "",
+            "0000",
+            false,
+            Create3Variant::Solady,
+            Some(1),
+        )
+    };
+    let mut state = new_state();
+    generate_salt_resumable(&mut state, 1, &stop, None, None).unwrap();
+    let mut group = c.benchmark_group("seeded search");
+    group.throughput(Throughput::Elements(state.next));
+
+    for (name, thread_count) in [("single-threaded", 1), ("multi-threaded", 6)] {
+        group.bench_function(name, |b| {
+            b.iter(|| generate_salt_resumable(&mut new_state(), thread_count, &stop, None, None))
+        });
+    }
+    group.finish();
+}
+
+fn calc_addr_batch_benchmark(c: &mut Criterion) {
+    let deployer: Address = black_box("0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap());
+    let inputs: Vec<(Address, Salt)> = (0..10_000u64)
+        .map(|i| {
+            let mut salt = [0u8; 32];
+            salt[24..].copy_from_slice(&i.to_be_bytes());
+            (deployer, Salt(salt))
+        })
+        .collect();
+
+    c.bench_function("single-threaded batch address", |b| {
+        b.iter(|| calc_addr_batch(&inputs, Create3Variant::Solady, 1))
+    });
+    c.bench_function("multi-threaded batch address", |b| {
+        b.iter(|| calc_addr_batch(&inputs, Create3Variant::Solady, 6))
     });
 }
 
 criterion_group!(
     name = generate_salt_benches;
     config = Criterion::default().measurement_time(Duration::from_secs(15));
-    targets = generate_salt_benchmark, generate_salt_multithread_benchmark, generate_salt_prefix_benchmark, generate_salt_prefix_multithread_benchmark
+    targets = generate_salt_benchmark, generate_salt_multithread_benchmark, generate_salt_prefix_benchmark, generate_salt_prefix_multithread_benchmark, generate_raw_salt_benchmark
+);
+criterion_group!(
+    name = batch_benches;
+    config = Criterion::default();
+    targets = calc_addr_batch_benchmark
+);
+criterion_group!(
+    name = search_benches;
+    config = Criterion::default().measurement_time(Duration::from_secs(15));
+    targets = seeded_search_benchmark
 );
-criterion_main!(generate_salt_benches);
+criterion_main!(generate_salt_benches, batch_benches, search_benches);
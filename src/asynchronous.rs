@@ -0,0 +1,250 @@
+use std::{
+    future::Future,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    },
+    task::{Context, Poll},
+};
+
+use futures_core::Stream;
+use tokio::{sync::mpsc, task};
+
+use crate::{
+    errors::Create3GenerateSaltError, matches::salt_matches, search::search_until, Address,
+    CancellationToken, MinedSalt, Pattern, SaltScheme, SearchLimits,
+};
+
+/// How many found salts a `SaltStream` holds before its search waits for them to be taken.
+const STREAM_CAPACITY: usize = 16;
+
+/// Stops the search holding a clone of the flag when dropped, so dropping a future or stream cancels its search.
+#[derive(Debug)]
+struct StopOnDrop(Arc<AtomicBool>);
+
+impl Drop for StopOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Generates a salt for a given deployer, salt prefix and address pattern without blocking the async executor.
+///
+/// The search runs on the blocking thread pool of the current tokio runtime, from where it starts its own worker
+/// threads. Dropping the future stops the search.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt_prefix` - A string representing the prefix to append to the generated salt.
+/// * `pattern` - The pattern the resulting address should match, case-insensitive or checksummed (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
+///
+/// # Returns
+///
+/// A future resolving to the generated salt, with its preimage string, the resulting address and how it was found. It
+/// borrows none of the arguments, so it can be spawned.
+pub fn generate_salt_async(
+    deployer: &Address,
+    salt_prefix: &str,
+    pattern: &Pattern,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> impl Future<Output = Result<MinedSalt, Create3GenerateSaltError>> + Send + 'static {
+    let (deployer, salt_prefix, pattern) = (*deployer, salt_prefix.to_owned(), pattern.clone());
+    let (scheme, limits) = (scheme.into(), limits.clone());
+
+    async move {
+        let stop = StopOnDrop(Arc::new(AtomicBool::new(false)));
+        let flag = stop.0.clone();
+        let search = task::spawn_blocking(move || {
+            search_until(
+                &deployer,
+                &salt_prefix,
+                &pattern,
+                thread_count,
+                scheme,
+                &limits,
+                &flag,
+            )
+        });
+        let result = search
+            .await
+            .unwrap_or(Err(Create3GenerateSaltError::WorkerPanicked));
+        drop(stop);
+        result
+    }
+}
+
+/// Starts a search that keeps generating salts for a given deployer, salt prefix and address pattern, and returns
+/// them as a `Stream`.
+///
+/// The search runs on the blocking thread pool of the current tokio runtime, so this panics outside of one. It yields
+/// the same distinct matches as `salt_matches`, and waits while found salts are not taken. Dropping the stream stops
+/// the search.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt_prefix` - A string representing the prefix to append to the generated salts.
+/// * `pattern` - The pattern the resulting addresses should match, case-insensitive or checksummed (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salts are mined for, optionally with a salt encoding
+///   and the `msg.sender` the factory binds into the salt.
+///
+/// # Returns
+///
+/// A stream of the generated salts, which ends after an error.
+pub fn salt_stream(
+    deployer: &Address,
+    salt_prefix: &str,
+    pattern: &Pattern,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+) -> SaltStream {
+    let stop = StopOnDrop(Arc::new(AtomicBool::new(false)));
+    let matches = salt_matches(deployer, salt_prefix, pattern, thread_count, scheme)
+        .with_cancellation(CancellationToken::from(stop.0.clone()));
+    let (sender, receiver) = mpsc::channel(STREAM_CAPACITY);
+
+    task::spawn_blocking(move || {
+        for found in matches {
+            if sender.blocking_send(found).is_err() {
+                return;
+            }
+        }
+    });
+
+    SaltStream {
+        receiver,
+        _stop: stop,
+    }
+}
+
+/// The salts found by a search started with `salt_stream`.
+#[derive(Debug)]
+pub struct SaltStream {
+    receiver: mpsc::Receiver<Result<MinedSalt, Create3GenerateSaltError>>,
+    _stop: StopOnDrop,
+}
+
+impl SaltStream {
+    /// Waits for the next salt, like `StreamExt::next` without importing it.
+    pub async fn recv(&mut self) -> Option<Result<MinedSalt, Create3GenerateSaltError>> {
+        self.receiver.recv().await
+    }
+}
+
+impl Stream for SaltStream {
+    type Item = Result<MinedSalt, Create3GenerateSaltError>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{future, pin::Pin, time::Duration};
+
+    use futures_core::Stream;
+
+    use crate::{
+        asynchronous::{generate_salt_async, salt_stream},
+        calc_addr_with_bytes, Address, Create3GenerateSaltError, Create3Variant, MinedSalt,
+        Pattern, SaltEncoding, SaltScheme, SearchLimits,
+    };
+
+    fn deployer() -> Address {
+        "5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap()
+    }
+
+    #[tokio::test]
+    async fn should_generate_salt_async() {
+        let pattern = Pattern::new("ab").unwrap();
+        let future = generate_salt_async(
+            &deployer(),
+            "pfx_",
+            &pattern,
+            2,
+            Create3Variant::Solady,
+            &SearchLimits::default(),
+        );
+        let mined = tokio::spawn(future).await.unwrap().unwrap();
+        assert!(mined.preimage.starts_with("pfx_"));
+        assert!(pattern.matches(&calc_addr_with_bytes(
+            &deployer(),
+            &mined.salt,
+            Create3Variant::Solady
+        )));
+
+        let impossible = Pattern::new(&"0".repeat(40)).unwrap();
+        let limits = SearchLimits::new().with_timeout(Duration::from_millis(50));
+        let result = generate_salt_async(
+            &deployer(),
+            "",
+            &impossible,
+            1,
+            Create3Variant::Solady,
+            &limits,
+        )
+        .await;
+        assert_eq!(result, Err(Create3GenerateSaltError::Timeout));
+    }
+
+    #[tokio::test]
+    async fn should_cancel_when_dropped() {
+        let impossible = Pattern::new(&"0".repeat(40)).unwrap();
+        let search = generate_salt_async(
+            &deployer(),
+            "",
+            &impossible,
+            1,
+            Create3Variant::Solady,
+            &SearchLimits::default(),
+        );
+        // The runtime waits for its blocking tasks when it shuts down, so this only ends if dropping stops them.
+        let timeout = tokio::time::timeout(Duration::from_millis(50), search).await;
+        assert!(timeout.is_err());
+
+        let mut stream = salt_stream(&deployer(), "", &impossible, 1, Create3Variant::Solady);
+        let next = tokio::time::timeout(Duration::from_millis(50), stream.recv()).await;
+        assert!(next.is_err());
+    }
+
+    #[tokio::test]
+    async fn should_stream_distinct_salts() {
+        let pattern = Pattern::new("a").unwrap();
+        let mut stream = salt_stream(&deployer(), "", &pattern, 2, Create3Variant::Solady);
+
+        let mut addresses = Vec::new();
+        while addresses.len() < 5 {
+            let next = future::poll_fn(|cx| Pin::new(&mut stream).poll_next(cx)).await;
+            let MinedSalt { salt, address, .. } = next.unwrap().unwrap();
+            assert_eq!(
+                calc_addr_with_bytes(&deployer(), &salt, Create3Variant::Solady),
+                address
+            );
+            assert!(pattern.matches(&address));
+            assert!(!addresses.contains(&address));
+            addresses.push(address);
+        }
+
+        // An error ends the stream. A raw salt prefix has to leave room for the 16 hex digits of the counter.
+        let raw = SaltScheme::new(Create3Variant::Solady, SaltEncoding::Raw);
+        let mut invalid = salt_stream(&deployer(), &"0".repeat(60), &pattern, 1, raw);
+        assert_eq!(
+            invalid.recv().await,
+            Some(Err(Create3GenerateSaltError::InvalidSaltPrefix))
+        );
+        assert_eq!(invalid.recv().await, None);
+    }
+}
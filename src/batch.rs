@@ -0,0 +1,174 @@
+#[cfg(not(feature = "rayon"))]
+use std::thread;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+use crate::{pool::resolve_thread_count, Address, DeployMode, Salt};
+
+/// Calculates the addresses for many (deployer, salt) pairs, spreading the work across threads, or across the current
+/// rayon pool with the `rayon` feature.
+///
+/// # Arguments
+///
+/// * `inputs` - The (deployer, salt) pairs, where each salt is directly used as the salt input.
+/// * `mode` - The CREATE3 factory or CREATE2 init code the addresses are calculated for.
+/// * `thread_count` - The number of threads to split the inputs across. `0` uses one per available core, or one per
+///   thread of the current rayon pool with the `rayon` feature, and `1` computes on the calling thread.
+///
+/// # Returns
+///
+/// The address of each input, in the same order as `inputs`.
+pub fn calc_addr_batch(
+    inputs: &[(Address, Salt)],
+    mode: impl Into<DeployMode>,
+    thread_count: usize,
+) -> Vec<Address> {
+    let mut out = vec![Address::default(); inputs.len()];
+    calc_addr_batch_into(inputs, mode, &mut out, thread_count);
+    out
+}
+
+/// Calculates the addresses for many (deployer, salt) pairs into a caller-provided buffer, spreading the work
+/// across threads.
+///
+/// # Arguments
+///
+/// * `inputs` - The (deployer, salt) pairs, where each salt is directly used as the salt input.
+/// * `mode` - The CREATE3 factory or CREATE2 init code the addresses are calculated for.
+/// * `out` - The buffer the address of each input is written to, in the same order as `inputs`.
+/// * `thread_count` - The number of threads to split the inputs across. `0` uses one per available core, or one per
+///   thread of the current rayon pool with the `rayon` feature, and `1` computes on the calling thread.
+///
+/// # Panics
+///
+/// Panics if `inputs` and `out` have different lengths.
+pub fn calc_addr_batch_into(
+    inputs: &[(Address, Salt)],
+    mode: impl Into<DeployMode>,
+    out: &mut [Address],
+    thread_count: usize,
+) {
+    assert_eq!(
+        inputs.len(),
+        out.len(),
+        "inputs and out must have the same length"
+    );
+    let mode = mode.into();
+
+    // Every thread gets one contiguous chunk, so results land in order without any synchronisation.
+    let chunk_size = chunk_size(inputs.len(), thread_count);
+    if chunk_size >= inputs.len() {
+        calc_chunk(inputs, mode, out);
+        return;
+    }
+
+    #[cfg(not(feature = "rayon"))]
+    thread::scope(|scope| {
+        for (inputs, out) in inputs.chunks(chunk_size).zip(out.chunks_mut(chunk_size)) {
+            scope.spawn(move || calc_chunk(inputs, mode, out));
+        }
+    });
+    // With the `rayon` feature the chunks are jobs on the current rayon pool instead.
+    #[cfg(feature = "rayon")]
+    inputs
+        .par_chunks(chunk_size)
+        .zip(out.par_chunks_mut(chunk_size))
+        .for_each(|(inputs, out)| calc_chunk(inputs, mode, out));
+}
+
+/// Lazily calculates the addresses for an iterator of (deployer, salt) pairs on the calling thread.
+///
+/// # Arguments
+///
+/// * `inputs` - The (deployer, salt) pairs, where each salt is directly used as the salt input.
+/// * `mode` - The CREATE3 factory or CREATE2 init code the addresses are calculated for.
+///
+/// # Returns
+///
+/// An iterator yielding the address of each input, in the same order as `inputs`.
+pub fn calc_addr_iter<I>(inputs: I, mode: impl Into<DeployMode>) -> impl Iterator<Item = Address>
+where
+    I: IntoIterator<Item = (Address, Salt)>,
+{
+    let mode = mode.into();
+    inputs
+        .into_iter()
+        .map(move |(deployer, salt)| mode.calc_addr_with_bytes(&deployer, &salt))
+}
+
+/// Returns the number of inputs given to each thread, which covers all of them if a single thread is used.
+fn chunk_size(len: usize, thread_count: usize) -> usize {
+    len.div_ceil(resolve_thread_count(thread_count))
+}
+
+fn calc_chunk(inputs: &[(Address, Salt)], mode: DeployMode, out: &mut [Address]) {
+    for ((deployer, salt), address) in inputs.iter().zip(out.iter_mut()) {
+        *address = mode.calc_addr_with_bytes(deployer, salt);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        batch::{calc_addr_batch, calc_addr_batch_into, calc_addr_iter, chunk_size},
+        calc_addr_with_bytes,
+        pool::resolve_thread_count,
+        Address, Create3Variant, Salt,
+    };
+
+    fn inputs(count: usize) -> Vec<(Address, Salt)> {
+        (0..count)
+            .map(|i| {
+                let mut deployer = [0u8; 20];
+                deployer[..8].copy_from_slice(&(i as u64 % 3).to_be_bytes());
+                let mut salt = [0u8; 32];
+                salt[..8].copy_from_slice(&(i as u64).to_be_bytes());
+                (Address(deployer), Salt(salt))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn should_match_calc_addr_in_order() {
+        let inputs = inputs(101);
+        let expected: Vec<Address> = inputs
+            .iter()
+            .map(|(deployer, salt)| calc_addr_with_bytes(deployer, salt, Create3Variant::Solady))
+            .collect();
+
+        for thread_count in [0, 1, 2, 7, 200] {
+            assert_eq!(
+                calc_addr_batch(&inputs, Create3Variant::Solady, thread_count),
+                expected
+            );
+        }
+        assert_eq!(
+            calc_addr_iter(inputs, Create3Variant::Solady).collect::<Vec<_>>(),
+            expected
+        );
+    }
+
+    #[test]
+    fn should_split_across_every_thread() {
+        // `0` spreads the inputs across one thread per core, like the miners.
+        let threads = resolve_thread_count(0);
+        assert_eq!(chunk_size(101, 0), 101usize.div_ceil(threads));
+        assert_eq!(chunk_size(101, 0) < 101, threads > 1);
+        assert_eq!(chunk_size(101, 1), 101);
+        assert_eq!(chunk_size(101, 7), 15);
+        assert_eq!(chunk_size(0, 4), 0);
+    }
+
+    #[test]
+    fn should_handle_empty_input() {
+        assert!(calc_addr_batch(&[], Create3Variant::Solady, 4).is_empty());
+    }
+
+    #[test]
+    #[should_panic]
+    fn calc_addr_batch_into_should_panic_if_lengths_differ() {
+        let mut out = [Address::default(); 2];
+        calc_addr_batch_into(&inputs(3), Create3Variant::Solady, &mut out, 2);
+    }
+}
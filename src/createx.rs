@@ -0,0 +1,439 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Instant,
+};
+
+use rand::Rng;
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    calc_addr_with_bytes,
+    errors::{Create3GenerateSaltError, CreateXSaltError},
+    pool::Pool,
+    search::{report, watch},
+    Address, Create3Variant, MinedSalt, Pattern, Salt, SaltEncoding, SaltScheme, SearchLimits,
+};
+
+/// Address of the CreateX factory, which is the same on every chain it has been deployed to.
+pub const CREATEX_ADDRESS: Address = Address([
+    0xba, 0x5e, 0xd0, 0x99, 0x63, 0x3d, 0x3b, 0x31, 0x3e, 0x4d, 0x5f, 0x7b, 0xdc, 0x13, 0x05, 0xd3,
+    0xc2, 0x8b, 0xa5, 0xed,
+]);
+
+/// The deploy protections CreateX reads from the first 21 bytes of a salt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct CreateXProtection {
+    /// Only the caller encoded in the salt can deploy to the resulting address.
+    pub permissioned: bool,
+    /// The resulting address differs on every chain.
+    pub cross_chain: bool,
+}
+
+/// Builds the raw salt passed to CreateX's `deployCreate3`.
+///
+/// # Arguments
+///
+/// * `caller` - The address calling CreateX (`msg.sender`).
+/// * `protection` - The deploy protections to encode into the salt.
+/// * `entropy` - The 11 trailing bytes of the salt that are free to choose.
+///
+/// # Returns
+///
+/// A 32-byte salt laid out as `[caller or zero address] ++ [cross-chain flag] ++ [entropy]`.
+pub fn build_salt(caller: &Address, protection: CreateXProtection, entropy: &[u8; 11]) -> Salt {
+    let mut salt = [0u8; 32];
+    if protection.permissioned {
+        salt[..20].copy_from_slice(&caller.0);
+    }
+    salt[20] = protection.cross_chain as u8;
+    salt[21..].copy_from_slice(entropy);
+    Salt(salt)
+}
+
+/// Computes the salt CreateX actually deploys with, following its `_guard` function.
+///
+/// # Arguments
+///
+/// * `salt` - The raw salt passed to CreateX.
+/// * `caller` - The address calling CreateX (`msg.sender`).
+/// * `chain_id` - The chain the contract is deployed on (`block.chainid`).
+///
+/// # Returns
+///
+/// The guarded 32-byte salt, or an error for the salts CreateX rejects with `InvalidSalt`.
+pub fn guard_salt(salt: &Salt, caller: &Address, chain_id: u64) -> Result<Salt, CreateXSaltError> {
+    let sender_bytes = &salt.0[..20];
+    let flag = salt.0[20];
+    let is_caller = sender_bytes == caller.0;
+    let is_zero = sender_bytes.iter().all(|b| *b == 0);
+
+    let mut chain_id_word = [0u8; 32];
+    chain_id_word[24..].copy_from_slice(&chain_id.to_be_bytes());
+    let mut caller_word = [0u8; 32];
+    caller_word[12..].copy_from_slice(&caller.0);
+
+    let mut hasher = Keccak256::new();
+    match (is_caller, is_zero, flag) {
+        // keccak256(abi.encode(msg.sender, block.chainid, salt))
+        (true, _, 0x01) => {
+            hasher.update(caller_word);
+            hasher.update(chain_id_word);
+        }
+        // keccak256(bytes32(uint256(uint160(msg.sender))) ++ salt)
+        (true, _, 0x00) => hasher.update(caller_word),
+        (true, _, _) => return Err(CreateXSaltError::InvalidSalt),
+        // keccak256(bytes32(block.chainid) ++ salt)
+        (false, true, 0x01) => hasher.update(chain_id_word),
+        (false, true, 0x02..) => return Err(CreateXSaltError::InvalidSalt),
+        // keccak256(abi.encode(salt))
+        _ => {}
+    }
+    hasher.update(salt);
+
+    Ok(Salt(hasher.finalize().into()))
+}
+
+/// Calculates the address of a contract deployed through CreateX's `deployCreate3`.
+///
+/// # Arguments
+///
+/// * `deployer` - The CreateX address, usually `CREATEX_ADDRESS`.
+/// * `salt` - The raw salt passed to CreateX.
+/// * `caller` - The address calling CreateX (`msg.sender`).
+/// * `chain_id` - The chain the contract is deployed on (`block.chainid`).
+///
+/// # Returns
+///
+/// The address of the contract.
+pub fn calc_addr(
+    deployer: &Address,
+    salt: &Salt,
+    caller: &Address,
+    chain_id: u64,
+) -> Result<Address, CreateXSaltError> {
+    let guarded = guard_salt(salt, caller, chain_id)?;
+    Ok(calc_addr_with_bytes(
+        deployer,
+        &guarded,
+        Create3Variant::CreateX,
+    ))
+}
+
+/// Generates a CreateX salt for a given address pattern by searching only the entropy bytes.
+///
+/// # Arguments
+///
+/// * `deployer` - The CreateX address, usually `CREATEX_ADDRESS`.
+/// * `caller` - The address calling CreateX (`msg.sender`).
+/// * `chain_id` - The chain the contract is deployed on (`block.chainid`).
+/// * `protection` - The deploy protections to encode into the salt.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error, and how it reports its progress.
+///   `SearchLimits::default()` never gives up.
+///
+/// # Returns
+///
+/// The generated salt, whose `salt` is the raw salt to pass to `deployCreate3` and whose preimage is its hex string,
+/// with the resulting address and how it was found. Its scheme is `Create3Variant::CreateX` with the raw encoding, as
+/// CreateX guards the salt itself (see `calc_addr`).
+pub fn generate_salt(
+    deployer: &Address,
+    caller: &Address,
+    chain_id: u64,
+    protection: CreateXProtection,
+    pattern: &str,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    generate_salt_multithread(deployer, caller, chain_id, protection, pattern, 1, limits)
+}
+
+/// Generates a CreateX salt for a given address pattern by searching only the entropy bytes on multiple threads.
+///
+/// # Arguments
+///
+/// * `deployer` - The CreateX address, usually `CREATEX_ADDRESS`.
+/// * `caller` - The address calling CreateX (`msg.sender`).
+/// * `chain_id` - The chain the contract is deployed on (`block.chainid`).
+/// * `protection` - The deploy protections to encode into the salt.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error, and how it reports its progress.
+///   `SearchLimits::default()` never gives up.
+///
+/// # Returns
+///
+/// The generated salt, whose `salt` is the raw salt to pass to `deployCreate3` and whose preimage is its hex string,
+/// with the resulting address and how it was found. Its scheme is `Create3Variant::CreateX` with the raw encoding, as
+/// CreateX guards the salt itself (see `calc_addr`).
+pub fn generate_salt_multithread(
+    deployer: &Address,
+    caller: &Address,
+    chain_id: u64,
+    protection: CreateXProtection,
+    pattern: &str,
+    thread_count: usize,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    let pattern = Pattern::new(pattern)?;
+    let scheme = SaltScheme::new(Create3Variant::CreateX, SaltEncoding::Raw);
+    let base: [u8; 11] = rand::thread_rng().gen();
+
+    let pool = Pool::new(thread_count);
+    let thread_count = pool.thread_count() as u64;
+    let attempts: Vec<AtomicU64> = (0..thread_count).map(|_| AtomicU64::new(0)).collect();
+    let end = limits.end(0);
+    let started = Instant::now();
+    let found: Mutex<Option<MinedSalt>> = Mutex::new(None);
+
+    pool.run(
+        |thread| {
+            // Every thread walks its own disjoint counters, added to the last 8 bytes of a random entropy, and stops
+            // on its own once a limit is hit.
+            let mut entropy = base;
+            let start = u64::from_be_bytes(base[3..].try_into().unwrap());
+            let mut counter = thread as u64;
+
+            while !pool.is_stopped()
+                && counter < end
+                && !limits.is_cancelled()
+                && !limits.is_expired()
+            {
+                entropy[3..].copy_from_slice(&start.wrapping_add(counter).to_be_bytes());
+                counter += thread_count;
+                let salt = build_salt(caller, protection, &entropy);
+                let guarded = guard_salt(&salt, caller, chain_id).unwrap();
+                let vanity_addr = calc_addr_with_bytes(deployer, &guarded, Create3Variant::CreateX);
+                attempts[thread].fetch_add(1, Ordering::Relaxed);
+
+                if pattern.matches(&vanity_addr) {
+                    found.lock().unwrap().get_or_insert_with(|| MinedSalt {
+                        preimage: salt.to_string(),
+                        salt,
+                        address: vanity_addr,
+                        deployer: *deployer,
+                        scheme,
+                        pattern: pattern.to_string(),
+                        checksum: false,
+                        // Counted once the search stops.
+                        attempts: 0,
+                        elapsed: started.elapsed(),
+                    });
+                    pool.stop();
+                }
+            }
+        },
+        || {
+            let report = || report(&attempts, started, pattern.expected_attempts());
+            watch(&pool, limits, report, || Ok(()))
+        },
+    )?;
+    pool.result()?;
+
+    // Without a salt, the threads stopped because of the limits.
+    let mut mined = found.into_inner().unwrap().ok_or_else(|| limits.error())?;
+    mined.attempts = attempts
+        .iter()
+        .map(|attempts| attempts.load(Ordering::Relaxed))
+        .sum();
+    Ok(mined)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use sha3::{Digest, Keccak256};
+
+    use crate::{
+        createx::{
+            build_salt, calc_addr, generate_salt, generate_salt_multithread, guard_salt,
+            CreateXProtection, CREATEX_ADDRESS,
+        },
+        errors::{Create3GenerateSaltError, CreateXSaltError},
+        Address, CancellationToken, Create3Variant, SearchLimits,
+    };
+
+    const CALLER: Address = Address([0x11; 20]);
+
+    #[test]
+    fn should_build_salt_layout() {
+        let entropy = [0xab; 11];
+        let salt = build_salt(
+            &CALLER,
+            CreateXProtection {
+                permissioned: true,
+                cross_chain: true,
+            },
+            &entropy,
+        );
+        assert_eq!(salt.0[..20], CALLER.0);
+        assert_eq!(salt.0[20], 0x01);
+        assert_eq!(salt.0[21..], entropy);
+
+        let salt = build_salt(&CALLER, CreateXProtection::default(), &entropy);
+        assert_eq!(salt.0[..21], [0u8; 21]);
+    }
+
+    #[test]
+    fn should_guard_salt_like_createx() {
+        let entropy = [0xab; 11];
+        let chain_id: u64 = 10;
+        let mut caller_word = [0u8; 32];
+        caller_word[12..].copy_from_slice(&CALLER.0);
+        let mut chain_id_word = [0u8; 32];
+        chain_id_word[31] = 10;
+
+        let runs = [
+            (true, true, [&caller_word[..], &chain_id_word[..]].concat()),
+            (true, false, caller_word.to_vec()),
+            (false, true, chain_id_word.to_vec()),
+            (false, false, vec![]),
+        ];
+        for (permissioned, cross_chain, preimage) in runs.iter() {
+            let protection = CreateXProtection {
+                permissioned: *permissioned,
+                cross_chain: *cross_chain,
+            };
+            let salt = build_salt(&CALLER, protection, &entropy);
+            let expected = Keccak256::digest([&preimage[..], &salt.0[..]].concat());
+            assert_eq!(
+                guard_salt(&salt, &CALLER, chain_id).unwrap().0,
+                expected.as_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn guard_salt_should_error_if_flag_is_unspecified() {
+        let mut salt = build_salt(&CALLER, CreateXProtection::default(), &[0; 11]);
+        salt.0[20] = 0x02;
+        assert_eq!(
+            guard_salt(&salt, &CALLER, 1),
+            Err(CreateXSaltError::InvalidSalt)
+        );
+
+        salt.0[..20].copy_from_slice(&CALLER.0);
+        assert_eq!(
+            guard_salt(&salt, &CALLER, 1),
+            Err(CreateXSaltError::InvalidSalt)
+        );
+
+        // Salts starting with a random address are hashed whatever their flag is.
+        salt.0[0] = 0x12;
+        assert!(guard_salt(&salt, &CALLER, 1).is_ok());
+    }
+
+    #[test]
+    fn cross_chain_protection_should_change_address_per_chain() {
+        let salt = build_salt(
+            &CALLER,
+            CreateXProtection {
+                permissioned: false,
+                cross_chain: true,
+            },
+            &[7; 11],
+        );
+        assert_ne!(
+            calc_addr(&CREATEX_ADDRESS, &salt, &CALLER, 1),
+            calc_addr(&CREATEX_ADDRESS, &salt, &CALLER, 10)
+        );
+    }
+
+    #[test]
+    fn should_generate_with_prefix() {
+        let runs = ["0", "00", "abc", "DeF"];
+        let protection = CreateXProtection {
+            permissioned: true,
+            cross_chain: true,
+        };
+        for run in runs.iter() {
+            let mined = generate_salt(
+                &CREATEX_ADDRESS,
+                &CALLER,
+                1,
+                protection,
+                run,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            assert_eq!(mined.salt.0[..20], CALLER.0);
+            assert_eq!(mined.salt.0[20], 0x01);
+            assert_eq!(
+                mined.scheme.encoding.encode(&mined.preimage),
+                Ok(mined.salt)
+            );
+            let addr = calc_addr(&CREATEX_ADDRESS, &mined.salt, &CALLER, 1).unwrap();
+            assert_eq!(mined.address, addr);
+            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn should_generate_multithread_with_prefix() {
+        let runs = ["0", "00", "abc", "DeF"];
+        let protection = CreateXProtection {
+            permissioned: false,
+            cross_chain: true,
+        };
+        for run in runs.iter() {
+            let mined = generate_salt_multithread(
+                &CREATEX_ADDRESS,
+                &CALLER,
+                137,
+                protection,
+                run,
+                6,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            let guarded = guard_salt(&mined.salt, &CALLER, 137).unwrap();
+            let addr =
+                crate::calc_addr_with_bytes(&CREATEX_ADDRESS, &guarded, Create3Variant::CreateX);
+            assert_eq!(mined.address, addr);
+            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn should_give_up_on_an_impossible_pattern() {
+        // 40 nibbles will not be found by any of these searches.
+        let pattern = "0".repeat(40);
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        let runs = [
+            (
+                SearchLimits::new().with_max_attempts(1000),
+                Create3GenerateSaltError::Timeout,
+            ),
+            (
+                SearchLimits::new().with_timeout(Duration::from_millis(50)),
+                Create3GenerateSaltError::Timeout,
+            ),
+            (
+                SearchLimits::new().with_cancellation(cancelled),
+                Create3GenerateSaltError::Cancelled,
+            ),
+        ];
+
+        for (limits, err) in runs {
+            let start = Instant::now();
+            assert_eq!(
+                generate_salt_multithread(
+                    &CREATEX_ADDRESS,
+                    &CALLER,
+                    1,
+                    CreateXProtection::default(),
+                    &pattern,
+                    3,
+                    &limits
+                ),
+                Err(err)
+            );
+            assert!(start.elapsed() < Duration::from_secs(10));
+        }
+    }
+}
@@ -0,0 +1,114 @@
+use crate::{
+    calc_create2_addr, calc_create_addr, create2_preimage, rlp_encode_create, Address,
+    Create3Variant, InitCodeHash, Salt, RLP_CREATE_MAX_LEN,
+};
+
+/// Every intermediate step of a CREATE3 address derivation.
+///
+/// Useful to compare each stage against on-chain traces when a deployment lands at an unexpected address.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Create3Derivation {
+    /// The create3 deployer address.
+    pub deployer: Address,
+    /// The salt that is directly used as the salt input.
+    pub salt: Salt,
+    /// The variant the address was derived for.
+    pub variant: Create3Variant,
+    /// The keccak256 hash of the proxy init code.
+    pub proxy_init_code_hash: InitCodeHash,
+    /// The `0xff ++ deployer ++ salt ++ proxy_init_code_hash` preimage hashed by `CREATE2`.
+    pub create2_preimage: Vec<u8>,
+    /// The address of the proxy deployed with `CREATE2`.
+    pub proxy: Address,
+    /// The proxy's nonce when it deploys the contract.
+    pub proxy_nonce: u64,
+    /// The `rlp([proxy, proxy_nonce])` payload hashed by `CREATE`.
+    pub rlp_payload: Vec<u8>,
+    /// The address of the contract.
+    pub address: Address,
+}
+
+impl Create3Derivation {
+    /// Derives the address of a contract based on the given deployer and salt, keeping every intermediate step.
+    ///
+    /// # Arguments
+    ///
+    /// * `deployer` - The create3 deployer address.
+    /// * `salt` - The salt that is directly used as the salt input.
+    /// * `variant` - The CREATE3 factory the contract is deployed through.
+    ///
+    /// # Returns
+    ///
+    /// The derivation, whose `address` matches `calc_addr_with_bytes`.
+    pub fn new(deployer: &Address, salt: &Salt, variant: Create3Variant) -> Self {
+        let proxy_init_code_hash = variant.proxy_init_code_hash();
+        let proxy = calc_create2_addr(deployer, salt, &proxy_init_code_hash);
+        let proxy_nonce = 1;
+        let mut rlp = [0u8; RLP_CREATE_MAX_LEN];
+        let rlp_len = rlp_encode_create(&proxy, proxy_nonce, &mut rlp);
+
+        Create3Derivation {
+            deployer: *deployer,
+            salt: *salt,
+            variant,
+            proxy_init_code_hash,
+            create2_preimage: create2_preimage(deployer, salt, &proxy_init_code_hash),
+            proxy,
+            proxy_nonce,
+            rlp_payload: rlp[..rlp_len].to_vec(),
+            address: calc_create_addr(&proxy, proxy_nonce),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        calc_addr_with_bytes, calc_create2_addr, derivation::Create3Derivation, Address,
+        Create3Variant, Salt,
+    };
+
+    #[test]
+    fn should_match_calc_addr_with_bytes() {
+        let deployer: Address = "d8b934580fcE35a11B58C6D73aDeE468a2833fa8".parse().unwrap();
+        let salt: Salt = "3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb"
+            .parse()
+            .unwrap();
+
+        let derivation = Create3Derivation::new(&deployer, &salt, Create3Variant::Solady);
+
+        assert_eq!(
+            derivation.address,
+            calc_addr_with_bytes(&deployer, &salt, Create3Variant::Solady)
+        );
+        assert_eq!(
+            derivation.address.to_string(),
+            "0x442188F25da4ac213D55aE81F1BFB421a4eb4562"
+        );
+        assert_eq!(
+            derivation.proxy,
+            calc_create2_addr(&deployer, &salt, &derivation.proxy_init_code_hash)
+        );
+    }
+
+    #[test]
+    fn should_expose_preimages() {
+        let deployer = Address([0x11; 20]);
+        let salt = Salt([0x22; 32]);
+        let derivation = Create3Derivation::new(&deployer, &salt, Create3Variant::Solady);
+
+        assert_eq!(derivation.create2_preimage.len(), 85);
+        assert_eq!(derivation.create2_preimage[0], 0xff);
+        assert_eq!(derivation.create2_preimage[1..21], deployer.0);
+        assert_eq!(derivation.create2_preimage[21..53], salt.0);
+        assert_eq!(
+            derivation.create2_preimage[53..],
+            derivation.proxy_init_code_hash.0
+        );
+
+        assert_eq!(derivation.rlp_payload.len(), 23);
+        assert_eq!(derivation.rlp_payload[..2], [0xd6, 0x94]);
+        assert_eq!(derivation.rlp_payload[2..22], derivation.proxy.0);
+        assert_eq!(derivation.rlp_payload[22], 0x01);
+    }
+}
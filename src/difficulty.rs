@@ -0,0 +1,102 @@
+use std::time::Duration;
+
+use crate::Pattern;
+
+/// How many salts a pattern takes to find, for estimating how long a search will run before starting it.
+///
+/// Every salt produces a matching address with the same probability, so the number of salts tested until the first
+/// match follows a geometric distribution.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Difficulty {
+    /// The probability that a single salt produces a matching address.
+    pub probability: f64,
+    /// The expected number of salts to test before one matches.
+    pub expected_attempts: f64,
+    /// The number of salts to test to find a match with a 50% chance.
+    pub attempts_50: f64,
+    /// The number of salts to test to find a match with a 90% chance.
+    pub attempts_90: f64,
+    /// The number of salts to test to find a match with a 99% chance.
+    pub attempts_99: f64,
+}
+
+impl Difficulty {
+    /// Estimates the difficulty of a pattern, see `Pattern::expected_attempts`.
+    pub fn new(pattern: &Pattern) -> Self {
+        let probability = 1.0 / pattern.expected_attempts();
+        let attempts = |chance: f64| attempts_for(probability, chance);
+        Difficulty {
+            probability,
+            expected_attempts: 1.0 / probability,
+            attempts_50: attempts(0.5),
+            attempts_90: attempts(0.9),
+            attempts_99: attempts(0.99),
+        }
+    }
+
+    /// Returns the number of salts to test to find a match with the given chance, between 0 and 1.
+    pub fn attempts_for(&self, chance: f64) -> f64 {
+        attempts_for(self.probability, chance)
+    }
+
+    /// Returns how long testing `attempts` salts takes at `hashrate` salts per second, or `None` if it does not fit in
+    /// a `Duration`.
+    pub fn time_for(attempts: f64, hashrate: f64) -> Option<Duration> {
+        Duration::try_from_secs_f64(attempts / hashrate).ok()
+    }
+
+    /// Returns the expected time to find a match at `hashrate` salts per second, or `None` if it does not fit in a
+    /// `Duration`.
+    pub fn expected_time(&self, hashrate: f64) -> Option<Duration> {
+        Difficulty::time_for(self.expected_attempts, hashrate)
+    }
+}
+
+/// Solves `1 - (1 - probability)^attempts = chance` for `attempts`, rounded up to whole salts.
+fn attempts_for(probability: f64, chance: f64) -> f64 {
+    if probability >= 1.0 {
+        return 1.0;
+    }
+    // `ln_1p` keeps the precision of tiny probabilities, which `(1 - probability).ln()` rounds to 0.
+    ((-chance).ln_1p() / (-probability).ln_1p()).ceil().max(1.0)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{difficulty::Difficulty, Pattern};
+
+    #[test]
+    fn should_estimate_attempts() {
+        let difficulty = Difficulty::new(&Pattern::new("0000").unwrap());
+        assert_eq!(difficulty.expected_attempts, 65536.0);
+        assert_eq!(difficulty.attempts_50, 45426.0);
+        assert_eq!(difficulty.attempts_90, 150902.0);
+        assert_eq!(difficulty.attempts_99, 301803.0);
+        assert_eq!(difficulty.attempts_for(0.5), difficulty.attempts_50);
+
+        let trivial = Difficulty::new(&Pattern::new("").unwrap());
+        assert_eq!((trivial.attempts_50, trivial.attempts_99), (1.0, 1.0));
+
+        // 40 nibbles stay finite, far past anything a search can test.
+        let impossible = Difficulty::new(&Pattern::new_checksum(&"A".repeat(40)).unwrap());
+        assert!(impossible.attempts_99.is_finite() && impossible.attempts_99 > 1e60);
+    }
+
+    #[test]
+    fn should_estimate_time() {
+        let difficulty = Difficulty::new(&Pattern::new("*dead").unwrap());
+        assert_eq!(
+            difficulty.expected_time(65536.0),
+            Some(Duration::from_secs(1))
+        );
+        assert_eq!(
+            Difficulty::time_for(1000.0, 100.0),
+            Some(Duration::from_secs(10))
+        );
+
+        let impossible = Difficulty::new(&Pattern::new(&"0".repeat(40)).unwrap());
+        assert_eq!(impossible.expected_time(1e9), None);
+    }
+}
@@ -0,0 +1,526 @@
+use std::{fmt::Display, str::FromStr};
+
+use sha3::{Digest, Keccak256};
+
+use crate::{
+    errors::{Create3GenerateSaltError, SaltEncodingError},
+    Address, Create3Variant, DeployMode, Salt,
+};
+
+/// How a salt string is turned into the bytes32 salt passed to the factory.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SaltEncoding {
+    /// `keccak256(bytes(salt))`, which is also `keccak256(abi.encodePacked(salt))` for a single string.
+    #[default]
+    Keccak256,
+    /// `keccak256(abi.encode(salt))` for a single string.
+    AbiEncode,
+    /// `bytes32("salt")`: the UTF-8 bytes right-padded with zeros. The salt must be at most 32 bytes long.
+    RightPadded,
+    /// `bytes32(uint256(n))`: a decimal (or `0x` prefixed hex) number, left-padded with zeros.
+    Uint256,
+    /// The salt is a hex encoded bytes32 (optional `0x` prefix) that is used as is.
+    Raw,
+}
+
+impl SaltEncoding {
+    /// Encodes a salt string into the bytes32 salt passed to the factory.
+    pub fn encode(&self, salt: &str) -> Result<Salt, SaltEncodingError> {
+        match self {
+            SaltEncoding::Keccak256 => Ok(Salt(Keccak256::digest(salt.as_bytes()).into())),
+            SaltEncoding::AbiEncode => {
+                // offset ++ length ++ data right-padded to a multiple of 32 bytes
+                let mut hasher = Keccak256::new();
+                let mut word = [0u8; 32];
+                word[31] = 0x20;
+                hasher.update(word);
+                word[24..].copy_from_slice(&(salt.len() as u64).to_be_bytes());
+                hasher.update(word);
+                hasher.update(salt.as_bytes());
+                hasher.update(&[0u8; 32][..(32 - salt.len() % 32) % 32]);
+                Ok(Salt(hasher.finalize().into()))
+            }
+            SaltEncoding::RightPadded => {
+                if salt.len() > 32 {
+                    return Err(SaltEncodingError::TooLong);
+                }
+                let mut bytes = [0u8; 32];
+                bytes[..salt.len()].copy_from_slice(salt.as_bytes());
+                Ok(Salt(bytes))
+            }
+            SaltEncoding::Uint256 => parse_uint256(salt).map(Salt),
+            SaltEncoding::Raw => salt.parse().map_err(|_| SaltEncodingError::NotBytes32),
+        }
+    }
+
+    /// Formats `counter` as a salt string starting with `salt_prefix` that this encoding accepts.
+    ///
+    /// Every counter gives a distinct salt of fixed width: 11 alphanumeric characters, 20 decimal digits for numbers,
+    /// or hex digits filling raw salts up to 64 characters.
+    pub(crate) fn counter_salt(&self, salt_prefix: &str, counter: u64) -> String {
+        let mut salt = String::new();
+        self.write_counter_salt(salt_prefix, counter, &mut salt);
+        salt
+    }
+
+    /// Like `counter_salt`, but overwrites `salt`, so that a search loop can reuse one buffer.
+    pub(crate) fn write_counter_salt(&self, salt_prefix: &str, counter: u64, salt: &mut String) {
+        let (alphabet, width): (&[u8], usize) = match self {
+            SaltEncoding::Keccak256 | SaltEncoding::AbiEncode | SaltEncoding::RightPadded => {
+                (ALPHANUMERIC, 11)
+            }
+            SaltEncoding::Uint256 => (b"0123456789", 20),
+            SaltEncoding::Raw => (b"0123456789abcdef", raw_fill_len(salt_prefix)),
+        };
+
+        let mut digits = [alphabet[0]; 64];
+        let digits = &mut digits[..width];
+        let mut n = counter;
+        for digit in digits.iter_mut().rev() {
+            *digit = alphabet[(n % alphabet.len() as u64) as usize];
+            n /= alphabet.len() as u64;
+        }
+
+        salt.clear();
+        salt.push_str(salt_prefix);
+        // The alphabets are ASCII.
+        salt.push_str(std::str::from_utf8(digits).unwrap());
+    }
+
+    /// Checks that every salt generated with `counter_salt` from `salt_prefix` is distinct and can be encoded.
+    pub(crate) fn validate_salt_prefix(
+        &self,
+        salt_prefix: &str,
+    ) -> Result<(), Create3GenerateSaltError> {
+        // Raw salts need 16 hex digits to hold every counter.
+        if *self == SaltEncoding::Raw && raw_fill_len(salt_prefix) < 16 {
+            return Err(Create3GenerateSaltError::InvalidSaltPrefix);
+        }
+        self.encode(&self.counter_salt(salt_prefix, u64::MAX))
+            .map(|_| ())
+            .map_err(|_| Create3GenerateSaltError::InvalidSaltPrefix)
+    }
+}
+
+/// The characters of alphanumeric salts.
+const ALPHANUMERIC: &[u8] = b"0123456789ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz";
+
+/// The number of hex digits appended to `salt_prefix` to make a raw salt 64 hex digits long.
+fn raw_fill_len(salt_prefix: &str) -> usize {
+    64 - salt_prefix
+        .strip_prefix("0x")
+        .unwrap_or(salt_prefix)
+        .len()
+        .min(64)
+}
+
+/// Parses a decimal or `0x` prefixed hex number into a big endian 32-byte word.
+fn parse_uint256(salt: &str) -> Result<[u8; 32], SaltEncodingError> {
+    let salt = salt.trim();
+    let mut word = [0u8; 32];
+
+    if let Some(hex_digits) = salt.strip_prefix("0x") {
+        if hex_digits.is_empty() || !hex_digits.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(SaltEncodingError::NotANumber);
+        }
+        let hex_digits = hex_digits.trim_start_matches('0');
+        if hex_digits.len() > 64 {
+            return Err(SaltEncodingError::TooLong);
+        }
+        let padded = format!("{:0>64}", hex_digits);
+        hex::decode_to_slice(padded, &mut word).map_err(|_| SaltEncodingError::NotANumber)?;
+        return Ok(word);
+    }
+
+    if salt.is_empty() || !salt.chars().all(|c| c.is_ascii_digit()) {
+        return Err(SaltEncodingError::NotANumber);
+    }
+
+    // word = word * 10 + digit, from the least significant byte up
+    for digit in salt.bytes().map(|c| c - b'0') {
+        let mut carry = digit as u32;
+        for byte in word.iter_mut().rev() {
+            let value = *byte as u32 * 10 + carry;
+            *byte = value as u8;
+            carry = value >> 8;
+        }
+        if carry != 0 {
+            return Err(SaltEncodingError::TooLong);
+        }
+    }
+    Ok(word)
+}
+
+impl Display for SaltEncoding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SaltEncoding::Keccak256 => "keccak256",
+            SaltEncoding::AbiEncode => "abi-encode",
+            SaltEncoding::RightPadded => "bytes32",
+            SaltEncoding::Uint256 => "uint256",
+            SaltEncoding::Raw => "raw",
+        })
+    }
+}
+
+/// Parses `keccak256`, `abi-encode`, `bytes32`, `uint256` and `raw`.
+impl FromStr for SaltEncoding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "keccak256" | "keccak" => Ok(SaltEncoding::Keccak256),
+            "abi-encode" | "abi.encode" => Ok(SaltEncoding::AbiEncode),
+            "bytes32" | "right-padded" => Ok(SaltEncoding::RightPadded),
+            "uint256" => Ok(SaltEncoding::Uint256),
+            "raw" => Ok(SaltEncoding::Raw),
+            s => Err(format!("unknown salt encoding '{}'", s)),
+        }
+    }
+}
+
+/// How a factory hashes the account calling it (`msg.sender`) into the salt before deploying.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SenderBinding {
+    /// `keccak256(abi.encodePacked(sender, salt))`, as used by ZeframLou's CREATE3Factory.
+    Packed(Address),
+    /// `keccak256(abi.encode(sender, salt))`, as used by Axelar's Create3Deployer.
+    AbiEncode(Address),
+}
+
+impl SenderBinding {
+    /// Returns the account calling the factory.
+    pub fn sender(&self) -> Address {
+        match self {
+            SenderBinding::Packed(sender) | SenderBinding::AbiEncode(sender) => *sender,
+        }
+    }
+
+    /// Returns the salt the factory actually deploys with for the salt it is called with.
+    pub fn bind(&self, salt: &Salt) -> Salt {
+        let mut hasher = Keccak256::new();
+        match self {
+            SenderBinding::Packed(sender) => hasher.update(sender.0),
+            SenderBinding::AbiEncode(sender) => {
+                // An address is left-padded to a 32-byte word.
+                hasher.update([0u8; 12]);
+                hasher.update(sender.0);
+            }
+        }
+        hasher.update(salt.0);
+        Salt(hasher.finalize().into())
+    }
+}
+
+impl Display for SenderBinding {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SenderBinding::Packed(sender) => write!(f, "packed:{}", sender),
+            SenderBinding::AbiEncode(sender) => write!(f, "abi-encode:{}", sender),
+        }
+    }
+}
+
+/// Parses `packed:<address>` and `abi-encode:<address>`.
+impl FromStr for SenderBinding {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (kind, sender) = s
+            .trim()
+            .split_once(':')
+            .ok_or_else(|| format!("expected '<packed|abi-encode>:<address>', got '{}'", s))?;
+        let sender = Address::from_str_checksummed(sender).map_err(|e| e.to_string())?;
+        match kind.to_lowercase().as_str() {
+            "packed" => Ok(SenderBinding::Packed(sender)),
+            "abi-encode" | "abi.encode" => Ok(SenderBinding::AbiEncode(sender)),
+            kind => Err(format!("unknown sender binding '{}'", kind)),
+        }
+    }
+}
+
+/// How a salt string is turned into an address: its encoding, an optional sender binding and the deployment it is
+/// used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SaltScheme {
+    /// How the salt string is turned into the bytes32 salt.
+    pub encoding: SaltEncoding,
+    /// How the factory hashes `msg.sender` into the salt, if it does.
+    pub sender: Option<SenderBinding>,
+    /// The CREATE3 factory or CREATE2 init code the salt is used with.
+    pub mode: DeployMode,
+}
+
+impl SaltScheme {
+    /// Creates a scheme from a deploy mode and a salt encoding.
+    pub fn new(mode: impl Into<DeployMode>, encoding: SaltEncoding) -> Self {
+        SaltScheme {
+            encoding,
+            sender: None,
+            mode: mode.into(),
+        }
+    }
+
+    /// Returns the scheme for a factory that hashes `msg.sender` into the salt.
+    pub fn with_sender(self, sender: SenderBinding) -> Self {
+        SaltScheme {
+            sender: Some(sender),
+            ..self
+        }
+    }
+
+    /// Calculates the address for a salt string.
+    pub fn calc_addr(&self, deployer: &Address, salt: &str) -> Result<Address, SaltEncodingError> {
+        let salt = self.encoding.encode(salt)?;
+        Ok(self.calc_addr_with_bytes(deployer, &salt))
+    }
+
+    /// Calculates the address for the bytes32 salt passed to the factory, applying the sender binding.
+    pub fn calc_addr_with_bytes(&self, deployer: &Address, salt: &Salt) -> Address {
+        match self.sender {
+            Some(sender) => self.mode.calc_addr_with_bytes(deployer, &sender.bind(salt)),
+            None => self.mode.calc_addr_with_bytes(deployer, salt),
+        }
+    }
+}
+
+impl From<DeployMode> for SaltScheme {
+    fn from(mode: DeployMode) -> Self {
+        SaltScheme::new(mode, SaltEncoding::default())
+    }
+}
+
+impl From<Create3Variant> for SaltScheme {
+    fn from(variant: Create3Variant) -> Self {
+        SaltScheme::new(variant, SaltEncoding::default())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use sha3::{Digest, Keccak256};
+
+    use crate::{
+        encoding::{SaltEncoding, SaltScheme, SenderBinding},
+        errors::{Create3GenerateSaltError, SaltEncodingError},
+        Address, Create3Variant, Salt,
+    };
+
+    fn word(hex: &str) -> Salt {
+        format!("{:0>64}", hex).parse().unwrap()
+    }
+
+    #[test]
+    fn should_encode_keccak256() {
+        assert_eq!(
+            SaltEncoding::Keccak256.encode("a").unwrap().0,
+            Keccak256::digest(b"a").as_slice()
+        );
+    }
+
+    #[test]
+    fn should_encode_abi_encode() {
+        let runs = ["", "a", &"b".repeat(32), &"c".repeat(33)];
+        for run in runs.iter() {
+            let padded_len = run.len().div_ceil(32) * 32;
+            let mut encoded = word("20").0.to_vec();
+            encoded.extend_from_slice(&word(&format!("{:x}", run.len())).0);
+            encoded.extend_from_slice(run.as_bytes());
+            encoded.resize(64 + padded_len, 0);
+            assert_eq!(
+                SaltEncoding::AbiEncode.encode(run).unwrap().0,
+                Keccak256::digest(&encoded).as_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn should_encode_right_padded() {
+        let salt = SaltEncoding::RightPadded.encode("name").unwrap();
+        assert_eq!(salt.0[..4], *b"name");
+        assert_eq!(salt.0[4..], [0u8; 28]);
+        assert!(SaltEncoding::RightPadded.encode(&"a".repeat(32)).is_ok());
+        assert_eq!(
+            SaltEncoding::RightPadded.encode(&"a".repeat(33)),
+            Err(SaltEncodingError::TooLong)
+        );
+    }
+
+    #[test]
+    fn should_encode_uint256() {
+        let runs = [
+            ("0", word("0")),
+            ("255", word("ff")),
+            ("0x0100", word("100")),
+            ("18446744073709551616", word("10000000000000000")),
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935",
+                word(&"f".repeat(64)),
+            ),
+        ];
+        for (run, expected) in runs.iter() {
+            assert_eq!(SaltEncoding::Uint256.encode(run), Ok(*expected));
+        }
+
+        let errors = [
+            (
+                "115792089237316195423570985008687907853269984665640564039457584007913129639936",
+                SaltEncodingError::TooLong,
+            ),
+            ("12a", SaltEncodingError::NotANumber),
+            ("", SaltEncodingError::NotANumber),
+            ("0x", SaltEncodingError::NotANumber),
+        ];
+        for (run, err) in errors.iter() {
+            assert_eq!(SaltEncoding::Uint256.encode(run), Err(*err));
+        }
+    }
+
+    #[test]
+    fn should_encode_raw() {
+        let hex = "0x3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb";
+        assert_eq!(SaltEncoding::Raw.encode(hex), Ok(hex.parse().unwrap()));
+        assert_eq!(
+            SaltEncoding::Raw.encode("0x1234"),
+            Err(SaltEncodingError::NotBytes32)
+        );
+    }
+
+    #[test]
+    fn counter_salts_should_be_distinct_and_encodable() {
+        let runs = [
+            (SaltEncoding::Keccak256, "pfx_"),
+            (SaltEncoding::AbiEncode, "pfx_"),
+            (SaltEncoding::RightPadded, "pfx_"),
+            (SaltEncoding::Uint256, "42"),
+            (SaltEncoding::Raw, "0xdead"),
+        ];
+        for (encoding, salt_prefix) in runs.iter() {
+            assert!(encoding.validate_salt_prefix(salt_prefix).is_ok());
+            let mut salts = Vec::new();
+            for counter in [0, 1, 61, 62, 1 << 32, u64::MAX - 1, u64::MAX] {
+                let salt = encoding.counter_salt(salt_prefix, counter);
+                assert!(salt.starts_with(salt_prefix));
+                salts.push(encoding.encode(&salt).unwrap());
+            }
+            salts.sort();
+            salts.dedup();
+            assert_eq!(salts.len(), 7);
+        }
+
+        assert_eq!(SaltEncoding::Keccak256.counter_salt("", 62), "00000000010");
+        assert_eq!(
+            SaltEncoding::RightPadded.validate_salt_prefix(&"a".repeat(22)),
+            Err(Create3GenerateSaltError::InvalidSaltPrefix)
+        );
+        assert_eq!(
+            SaltEncoding::Uint256.validate_salt_prefix("pfx_"),
+            Err(Create3GenerateSaltError::InvalidSaltPrefix)
+        );
+        assert_eq!(
+            SaltEncoding::Raw.validate_salt_prefix(&"0".repeat(49)),
+            Err(Create3GenerateSaltError::InvalidSaltPrefix)
+        );
+    }
+
+    #[test]
+    fn should_parse_encodings() {
+        let runs = [
+            SaltEncoding::Keccak256,
+            SaltEncoding::AbiEncode,
+            SaltEncoding::RightPadded,
+            SaltEncoding::Uint256,
+            SaltEncoding::Raw,
+        ];
+        for run in runs.iter() {
+            assert_eq!(run.to_string().parse::<SaltEncoding>(), Ok(*run));
+        }
+    }
+
+    #[test]
+    fn scheme_should_calculate_with_encoding() {
+        let deployer: Address = "0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap();
+
+        // Matches the Solady answer for the keccak256 encoded salt "a"
+        let scheme = SaltScheme::from(Create3Variant::Solady);
+        assert_eq!(
+            scheme.calc_addr(&deployer, "a").unwrap().to_string(),
+            "0xBFf47440D3A5E59714F1D995F8b105E2a04AB46A"
+        );
+
+        let scheme = SaltScheme::new(Create3Variant::Solady, SaltEncoding::RightPadded);
+        assert_eq!(
+            scheme.calc_addr(&deployer, "a").unwrap(),
+            crate::calc_addr_with_bytes(
+                &deployer,
+                &SaltEncoding::RightPadded.encode("a").unwrap(),
+                Create3Variant::Solady
+            )
+        );
+    }
+
+    #[test]
+    fn should_bind_sender_into_salt() {
+        let sender: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let salt = Salt([7u8; 32]);
+
+        let mut packed = sender.0.to_vec();
+        packed.extend_from_slice(&salt.0);
+        assert_eq!(
+            SenderBinding::Packed(sender).bind(&salt).0,
+            Keccak256::digest(&packed).as_slice()
+        );
+
+        let mut encoded = [0u8; 12].to_vec();
+        encoded.extend_from_slice(&packed);
+        assert_eq!(
+            SenderBinding::AbiEncode(sender).bind(&salt).0,
+            Keccak256::digest(&encoded).as_slice()
+        );
+    }
+
+    #[test]
+    fn should_parse_sender_bindings() {
+        let sender: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let runs = [
+            SenderBinding::Packed(sender),
+            SenderBinding::AbiEncode(sender),
+        ];
+        for run in runs.iter() {
+            assert_eq!(run.to_string().parse::<SenderBinding>(), Ok(*run));
+            assert_eq!(run.sender(), sender);
+        }
+        assert!("packed".parse::<SenderBinding>().is_err());
+        assert!("packed:0x1234".parse::<SenderBinding>().is_err());
+        assert!(format!("other:{}", sender)
+            .parse::<SenderBinding>()
+            .is_err());
+    }
+
+    #[test]
+    fn scheme_should_calculate_with_sender_binding() {
+        let deployer: Address = "0x9fBB3DF7C40Da2e5A0dE984fFE2CCB7C47cd0ABf"
+            .parse()
+            .unwrap();
+        let sender: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let binding = SenderBinding::Packed(sender);
+        let scheme = SaltScheme::from(Create3Variant::ZeframLou).with_sender(binding);
+
+        let salt = SaltEncoding::Keccak256.encode("a").unwrap();
+        let expected =
+            crate::calc_addr_with_bytes(&deployer, &binding.bind(&salt), Create3Variant::ZeframLou);
+        assert_eq!(scheme.calc_addr(&deployer, "a"), Ok(expected));
+        assert_eq!(scheme.calc_addr_with_bytes(&deployer, &salt), expected);
+        assert_ne!(
+            SaltScheme::from(Create3Variant::ZeframLou).calc_addr(&deployer, "a"),
+            Ok(expected)
+        );
+    }
+}
@@ -3,10 +3,23 @@ use std::{error::Error, fmt::Display};
 /// Errors for generating a CREATE3 salt.
 #[derive(Debug, PartialEq)]
 pub enum Create3GenerateSaltError {
-    /// Occurs if the prefix is too long. The prefix must be less than or equal to 20 bytes.
-    PrefixTooLong,
-    // Occurs if the prefix is not a hex encoded string. The prefix must be in hexadecimal format.
-    PrefixNotHexEncoded,
+    /// Occurs if the pattern is too long. The pattern must have at most 40 nibbles, not counting `*`.
+    PatternTooLong,
+    /// Occurs if the pattern contains anything but hex digits, `?` and `*` after an optional `0x` or `0X`. `index` is
+    /// the position of `char` in the trimmed pattern.
+    InvalidPatternChar { index: usize, char: char },
+    /// Occurs if salts starting with the salt prefix cannot be encoded with the chosen salt encoding.
+    InvalidSaltPrefix,
+    /// Occurs if the checkpoint file of a resumable search cannot be written.
+    CheckpointFailed,
+    /// Occurs if more than 24 leading bytes of a raw salt are fixed, leaving fewer than 8 bytes to search.
+    TooManyFixedBytes,
+    /// Occurs if no salt was found before the deadline or within the maximum number of attempts.
+    Timeout,
+    /// Occurs if the search was cancelled before a salt was found.
+    Cancelled,
+    /// Occurs if a worker thread of a search panicked.
+    WorkerPanicked,
 }
 
 impl Error for Create3GenerateSaltError {
@@ -16,12 +29,108 @@ impl Error for Create3GenerateSaltError {
 }
 
 impl Display for Create3GenerateSaltError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let message = match self {
+            Create3GenerateSaltError::PatternTooLong => 
+                "Create3GenerateSaltError::PatternTooLong: the pattern is too long. The pattern must have at most 40 nibbles, not counting '*'.",
+            Create3GenerateSaltError::InvalidPatternChar { index, char } => 
+                return write!(f, "Create3GenerateSaltError::InvalidPatternChar: the pattern has an invalid character '{}' at index {}. The pattern may only contain hex digits, '?' and '*' after an optional '0x' or '0X'.", char, index),
+            Create3GenerateSaltError::InvalidSaltPrefix => 
+                "Create3GenerateSaltError::InvalidSaltPrefix: salts starting with the salt prefix cannot be encoded with the chosen salt encoding.",
+            Create3GenerateSaltError::CheckpointFailed => 
+                "Create3GenerateSaltError::CheckpointFailed: the checkpoint file cannot be written.",
+            Create3GenerateSaltError::TooManyFixedBytes => 
+                "Create3GenerateSaltError::TooManyFixedBytes: at most 24 leading bytes of a raw salt can be fixed.",
+            Create3GenerateSaltError::Timeout => 
+                "Create3GenerateSaltError::Timeout: no salt was found before the deadline or within the maximum number of attempts.",
+            Create3GenerateSaltError::Cancelled => 
+                "Create3GenerateSaltError::Cancelled: the search was cancelled before a salt was found.",
+            Create3GenerateSaltError::WorkerPanicked => 
+                "Create3GenerateSaltError::WorkerPanicked: a worker thread panicked during the search.",
+        };
+        f.write_str(message)
+    }
+}
+
+/// Errors for guarding a CreateX salt.
+#[derive(Debug, PartialEq)]
+pub enum CreateXSaltError {
+    /// Occurs if the 21st byte of a salt starting with the caller or the zero address is greater than `0x01`.
+    /// CreateX reverts with `InvalidSalt` to enforce an explicit cross-chain redeploy protection flag.
+    InvalidSalt,
+}
+
+impl Error for CreateXSaltError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for CreateXSaltError {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.write_str(match self {
-            Create3GenerateSaltError::PrefixTooLong => 
-                "Create3GenerateSaltError::PrefixTooLong: the prefix is too long. The prefix must be less than or equal to 20 bytes.",
-            Create3GenerateSaltError::PrefixNotHexEncoded => 
-                "Create3GenerateSaltError::PrefixNotHexEncoded: the prefix is not a hex encoded string. The prefix must be in hexadecimal format.",
+            CreateXSaltError::InvalidSalt => 
+                "CreateXSaltError::InvalidSalt: the redeploy protection flag must be 0x00 or 0x01 when the salt starts with the caller or the zero address.",
         })
     }
-}
\ No newline at end of file
+}
+
+/// Errors for parsing hex encoded addresses, salts and hashes.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Create3ParseError {
+    /// Occurs if the input is not a hex encoded string. An optional `0x` prefix is allowed.
+    NotHexEncoded,
+    /// Occurs if the input does not have the expected number of bytes.
+    InvalidLength,
+    /// Occurs if a mixed-case address does not match its EIP-55 checksum.
+    InvalidChecksum,
+}
+
+impl Error for Create3ParseError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for Create3ParseError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            Create3ParseError::NotHexEncoded => 
+                "Create3ParseError::NotHexEncoded: the input is not a hex encoded string. The input must be in hexadecimal format.",
+            Create3ParseError::InvalidLength => 
+                "Create3ParseError::InvalidLength: the input has an incorrect length.",
+            Create3ParseError::InvalidChecksum => 
+                "Create3ParseError::InvalidChecksum: the mixed-case address does not match its EIP-55 checksum.",
+        })
+    }
+}
+
+/// Errors for encoding a salt string into a bytes32 salt.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum SaltEncodingError {
+    /// Occurs if the salt does not fit in 32 bytes.
+    TooLong,
+    /// Occurs if a `uint256` salt is not a decimal or `0x` prefixed hex number.
+    NotANumber,
+    /// Occurs if a raw salt is not a hex encoded bytes32. An optional `0x` prefix is allowed.
+    NotBytes32,
+}
+
+impl Error for SaltEncodingError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        None
+    }
+}
+
+impl Display for SaltEncodingError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            SaltEncodingError::TooLong => 
+                "SaltEncodingError::TooLong: the salt does not fit in 32 bytes.",
+            SaltEncodingError::NotANumber => 
+                "SaltEncodingError::NotANumber: the salt is not a decimal or 0x prefixed hex number.",
+            SaltEncodingError::NotBytes32 => 
+                "SaltEncodingError::NotBytes32: the salt is not a hex encoded bytes32.",
+        })
+    }
+}
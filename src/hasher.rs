@@ -0,0 +1,133 @@
+#[cfg(not(feature = "simd"))]
+use sha3::{Digest, Keccak256};
+
+#[cfg(feature = "simd")]
+use crate::simd::Backend;
+use crate::{Address, DeployMode, InitCodeHash, Salt, SaltScheme, SenderBinding};
+
+/// The number of salts the miners hash per call of `AddressHasher::calc_addrs`.
+pub(crate) const BATCH: usize = 8;
+
+/// Calculates the addresses of many salts for one deployer and scheme, as done by the miners.
+///
+/// The `CREATE2` preimage and the `CREATE` preimage of a CREATE3 proxy are built in fixed buffers, so no attempt
+/// allocates. The whole 85-byte `CREATE2` preimage fits in a single keccak block, so every salt costs one permutation
+/// per hash whether or not the `0xff ++ deployer` part is absorbed ahead of time. With the `simd` feature, batches of
+/// salts are hashed several at a time.
+#[derive(Clone)]
+pub(crate) struct AddressHasher {
+    deployer: Address,
+    init_code_hash: InitCodeHash,
+    /// Whether the `CREATE2` address is a CREATE3 proxy that deploys the contract at nonce 1.
+    create3: bool,
+    sender: Option<SenderBinding>,
+    #[cfg(feature = "simd")]
+    backend: Backend,
+}
+
+impl AddressHasher {
+    pub(crate) fn new(deployer: &Address, scheme: &SaltScheme) -> Self {
+        let (init_code_hash, create3) = match scheme.mode {
+            DeployMode::Create2 { init_code_hash } => (init_code_hash, false),
+            DeployMode::Create3(variant) => (variant.proxy_init_code_hash(), true),
+        };
+
+        AddressHasher {
+            deployer: *deployer,
+            init_code_hash,
+            create3,
+            sender: scheme.sender,
+            #[cfg(feature = "simd")]
+            backend: Backend::detect(),
+        }
+    }
+
+    /// Calculates the addresses for a batch of bytes32 salts passed to the factory, like
+    /// `SaltScheme::calc_addr_with_bytes`.
+    pub(crate) fn calc_addrs(&self, salts: &[Salt; BATCH]) -> [Address; BATCH] {
+        let salts = match self.sender {
+            Some(sender) => salts.map(|salt| sender.bind(&salt)),
+            None => *salts,
+        };
+
+        let preimages = salts.map(|salt| self.create2_preimage(&salt));
+        let hashes = self.keccak256_batch(&preimages.each_ref().map(|preimage| &preimage[..]));
+        if !self.create3 {
+            return hashes.map(|hash| Address(hash[12..].try_into().unwrap()));
+        }
+
+        let rlps = hashes.map(|hash| proxy_rlp(&hash));
+        let hashes = self.keccak256_batch(&rlps.each_ref().map(|rlp| &rlp[..]));
+        hashes.map(|hash| Address(hash[12..].try_into().unwrap()))
+    }
+
+    /// Builds `0xff ++ deployer ++ salt ++ init_code_hash`, the preimage of a `CREATE2` address.
+    fn create2_preimage(&self, salt: &Salt) -> [u8; 85] {
+        let mut preimage = [0u8; 85];
+        preimage[0] = 0xff;
+        preimage[1..21].copy_from_slice(&self.deployer.0);
+        preimage[21..53].copy_from_slice(&salt.0);
+        preimage[53..].copy_from_slice(&self.init_code_hash.0);
+        preimage
+    }
+
+    /// Computes the keccak256 hash of a batch of messages, several at a time with the `simd` feature.
+    pub(crate) fn keccak256_batch(&self, messages: &[&[u8]; BATCH]) -> [[u8; 32]; BATCH] {
+        #[cfg(feature = "simd")]
+        return self.backend.keccak256(messages);
+        #[cfg(not(feature = "simd"))]
+        return messages.map(|message| Keccak256::digest(message).into());
+    }
+}
+
+/// Builds `rlp([proxy, 1])` for the `CREATE2` hash of a CREATE3 proxy, which is always a 23 byte list:
+/// `0xd6 ++ 0x94 ++ proxy ++ 0x01`.
+fn proxy_rlp(hash: &[u8; 32]) -> [u8; 23] {
+    let mut rlp = [0u8; 23];
+    rlp[0] = 0xd6;
+    rlp[1] = 0x94;
+    rlp[2..22].copy_from_slice(&hash[12..]);
+    rlp[22] = 0x01;
+    rlp
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+
+    use crate::{
+        hasher::{AddressHasher, BATCH},
+        Address, Create3Variant, DeployMode, InitCodeHash, Salt, SaltEncoding, SaltScheme,
+        SenderBinding,
+    };
+
+    #[test]
+    fn should_calculate_like_the_scheme() {
+        let deployer: Address = "5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let sender: Address = "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        let runs = [
+            SaltScheme::from(Create3Variant::Solady),
+            SaltScheme::from(Create3Variant::Custom {
+                proxy_init_code_hash: InitCodeHash([1u8; 32]),
+            }),
+            SaltScheme::from(DeployMode::Create2 {
+                init_code_hash: InitCodeHash([2u8; 32]),
+            }),
+            SaltScheme::new(Create3Variant::ZeframLou, SaltEncoding::RightPadded)
+                .with_sender(SenderBinding::Packed(sender)),
+            SaltScheme::from(Create3Variant::Solady).with_sender(SenderBinding::AbiEncode(sender)),
+        ];
+
+        let mut rng = rand::thread_rng();
+        for scheme in runs.iter() {
+            let hasher = AddressHasher::new(&deployer, scheme);
+            for _ in 0..3 {
+                let salts: [Salt; BATCH] = std::array::from_fn(|_| Salt(rng.gen()));
+                let addrs = hasher.calc_addrs(&salts);
+                for (salt, addr) in salts.iter().zip(addrs.iter()) {
+                    assert_eq!(*addr, scheme.calc_addr_with_bytes(&deployer, salt));
+                }
+            }
+        }
+    }
+}
@@ -1,292 +1,460 @@
+#[cfg(feature = "tokio")]
+pub mod asynchronous;
+pub mod batch;
+pub mod createx;
+pub mod derivation;
+pub mod difficulty;
+pub mod encoding;
 pub mod errors;
+mod hasher;
+pub mod matches;
+pub mod mined;
+pub mod multi;
+pub mod optimize;
+pub mod pattern;
+mod pool;
+pub mod raw;
+pub mod search;
+#[cfg(feature = "simd")]
+mod simd;
+pub mod types;
+pub mod variant;
 
-use std::{
-    sync::{Arc, RwLock},
-    thread,
-};
-
-use errors::Create3GenerateSaltError;
-use rand::{distributions::Alphanumeric, Rng};
+pub use derivation::Create3Derivation;
+pub use difficulty::Difficulty;
+pub use encoding::{SaltEncoding, SaltScheme, SenderBinding};
+use errors::{Create3GenerateSaltError, SaltEncodingError};
+pub use mined::MinedSalt;
+pub use pattern::Pattern;
+use search::search_once;
+pub use search::{CancellationToken, Progress, ProgressHook, SearchLimits};
 use sha3::{Digest, Keccak256};
+pub use types::{Address, InitCodeHash, Salt};
+pub use variant::{Create3Variant, DeployMode};
+
+/// Implements `Serialize` and `Deserialize` through `Display` and `FromStr`, so values are written the same way as in
+/// checkpoint files, e.g. addresses as checksummed hex.
+#[cfg(feature = "serde")]
+macro_rules! serde_via_str {
+    ($($name:ty),*) => {$(
+        impl serde::Serialize for $name {
+            fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                serializer.collect_str(self)
+            }
+        }
+
+        impl<'de> serde::Deserialize<'de> for $name {
+            fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                String::deserialize(deserializer)?
+                    .parse()
+                    .map_err(serde::de::Error::custom)
+            }
+        }
+    )*};
+}
 
-// Proxy bytecode - Deplyed contract bytecode doesn't effect the deterministic address.
-const KECCAK256_PROXY_CHILD_BYTECODE: [u8; 32] = [
-    33, 195, 93, 190, 27, 52, 74, 36, 136, 207, 51, 33, 214, 206, 84, 47, 142, 159, 48, 85, 68,
-    255, 9, 228, 153, 58, 98, 49, 154, 73, 124, 31,
-];
+#[cfg(feature = "serde")]
+serde_via_str!(
+    Address,
+    Salt,
+    InitCodeHash,
+    Create3Variant,
+    DeployMode,
+    SaltEncoding,
+    SenderBinding
+);
 
 /// Calculates the address of a contract based on the given deployer and salt.
 ///
 /// # Arguments
 ///
-/// * `deployer` - A byte slice representing the create3 deployer address.
+/// * `deployer` - The create3 deployer address.
 /// * `salt` - A string in u8 array format that is digested by keccak256 and used as the salt input.
+/// * `variant` - The CREATE3 factory the contract is deployed through.
 ///
 /// # Returns
 ///
-/// A 20-byte array representing the address of the contract.
+/// The address of the contract.
 // @dev note: keccak256(rlp([keccak256(0xff ++ address(this) ++ _salt ++ keccak256(childBytecode))[12:], 0x01]))
-pub fn calc_addr(deployer: &[u8], salt: &[u8]) -> [u8; 20] {
+pub fn calc_addr(deployer: &Address, salt: &[u8], variant: Create3Variant) -> Address {
     // [contract creation prefix] + [create3 deployer] + [salt] + [keccak256(childBytecode)]
-    let salt_hash = Keccak256::digest(salt);
-    calc_addr_with_bytes(deployer, &salt_hash.as_slice()[0..32].try_into().unwrap())
+    let salt_hash = Salt(Keccak256::digest(salt).into());
+    calc_addr_with_bytes(deployer, &salt_hash, variant)
+}
+
+/// Calculates the address of a contract based on the given deployer and a salt string in the given encoding.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt` - The salt string, turned into the salt input with `encoding`.
+/// * `encoding` - How the salt string is turned into bytes32, e.g. `bytes32("name")` or `bytes32(uint256(n))`.
+/// * `variant` - The CREATE3 factory the contract is deployed through.
+///
+/// # Returns
+///
+/// The address of the contract, or an error if the salt cannot be encoded.
+pub fn calc_addr_with_encoding(
+    deployer: &Address,
+    salt: &str,
+    encoding: SaltEncoding,
+    variant: Create3Variant,
+) -> Result<Address, SaltEncodingError> {
+    SaltScheme::new(variant, encoding).calc_addr(deployer, salt)
 }
 
 /// Calculates the address of a contract based on the given deployer and salt.
 ///
 /// # Arguments
 ///
-/// * `deployer` - A byte slice representing the create3 deployer address.
-/// * `salt` - Bytes in u8 array format that is directly used as the salt input.
+/// * `deployer` - The create3 deployer address.
+/// * `salt` - The salt that is directly used as the salt input.
+/// * `variant` - The CREATE3 factory the contract is deployed through.
 ///
 /// # Returns
 ///
-/// A 20-byte array representing the address of the contract.
-pub fn calc_addr_with_bytes(deployer: &[u8], salt: &[u8; 32]) -> [u8; 20] {
-    let mut bytes: Vec<u8> = Vec::new();
-    bytes.push(0xff);
-    bytes.extend_from_slice(deployer);
-    bytes.extend_from_slice(salt);
-    bytes.extend_from_slice(&KECCAK256_PROXY_CHILD_BYTECODE);
+/// The address of the contract.
+pub fn calc_addr_with_bytes(deployer: &Address, salt: &Salt, variant: Create3Variant) -> Address {
+    // [contract creation prefix] + [create3 deployer] + [salt] + [keccak256(childBytecode)]
+    let proxy = calc_create2_addr(deployer, salt, &variant.proxy_init_code_hash());
 
-    // println!("FINAL BYTES: {:?}, SALT: {:?}", bytes, salt);
+    // Use proxy address to compute the final contract address.
+    // The proxy is a fresh contract, so it deploys with nonce 1 (EIP-161).
+    calc_create_addr(&proxy, 1)
+}
 
-    let hash = Keccak256::digest(&bytes);
-    let mut proxy_bytes = [0u8; 20];
-    proxy_bytes.copy_from_slice(&hash[12..]);
+/// Calculates the address of a contract deployed through a factory that hashes `msg.sender` into the salt.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `sender` - The account calling the factory and how the factory binds it into the salt.
+/// * `salt` - A string in u8 array format that is digested by keccak256 and passed to the factory.
+/// * `variant` - The CREATE3 factory the contract is deployed through.
+///
+/// # Returns
+///
+/// The address of the contract.
+pub fn calc_addr_sender_bound(
+    deployer: &Address,
+    sender: &SenderBinding,
+    salt: &[u8],
+    variant: Create3Variant,
+) -> Address {
+    let salt_hash = Salt(Keccak256::digest(salt).into());
+    calc_addr_with_bytes_sender_bound(deployer, sender, &salt_hash, variant)
+}
 
-    // Use proxy address to compute the final contract address.
-    // keccak256(rlp(proxy_bytes ++ 0x01)) More here -> https://ethereum.stackexchange.com/a/761/66849
-    let mut bytes2: Vec<u8> = Vec::new();
-    bytes2.extend_from_slice(&[0xd6, 0x94]);
-    // RLP prefix for a list of two items
-    bytes2.extend(&proxy_bytes);
-    // The proxy address
-    bytes2.push(0x01);
-    // The nonce of the contract
-    let hash2 = Keccak256::digest(&bytes2);
+/// Calculates the address of a contract deployed through a factory that hashes `msg.sender` into the salt.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `sender` - The account calling the factory and how the factory binds it into the salt.
+/// * `salt` - The salt that is passed to the factory.
+/// * `variant` - The CREATE3 factory the contract is deployed through.
+///
+/// # Returns
+///
+/// The address of the contract.
+pub fn calc_addr_with_bytes_sender_bound(
+    deployer: &Address,
+    sender: &SenderBinding,
+    salt: &Salt,
+    variant: Create3Variant,
+) -> Address {
+    calc_addr_with_bytes(deployer, &sender.bind(salt), variant)
+}
+
+/// Calculates the address of a contract deployed with `CREATE2`.
+///
+/// # Arguments
+///
+/// * `deployer` - The create2 deployer address.
+/// * `salt` - The salt that is directly used as the salt input.
+/// * `init_code_hash` - The keccak256 hash of the contract's init code.
+///
+/// # Returns
+///
+/// The address of the contract.
+// @dev note: keccak256(0xff ++ deployer ++ salt ++ keccak256(initCode))[12:]
+pub fn calc_create2_addr(
+    deployer: &Address,
+    salt: &Salt,
+    init_code_hash: &InitCodeHash,
+) -> Address {
+    // Hash the preimage piece by piece to avoid allocating it.
+    let mut hasher = Keccak256::new();
+    hasher.update([0xff]);
+    hasher.update(deployer.0);
+    hasher.update(salt.0);
+    hasher.update(init_code_hash.0);
+
+    let hash = hasher.finalize();
+    let mut address = [0u8; 20];
+    address.copy_from_slice(&hash[12..]);
+    Address(address)
+}
+
+/// Calculates the address of a contract deployed with `CREATE` by the given sender.
+///
+/// # Arguments
+///
+/// * `sender` - The deploying address.
+/// * `nonce` - The nonce of the sender at the time of deployment.
+///
+/// # Returns
+///
+/// The address of the contract.
+// @dev note: keccak256(rlp([sender, nonce]))[12:] More here -> https://ethereum.stackexchange.com/a/761/66849
+pub fn calc_create_addr(sender: &Address, nonce: u64) -> Address {
+    let mut rlp = [0u8; RLP_CREATE_MAX_LEN];
+    let len = rlp_encode_create(sender, nonce, &mut rlp);
+    let hash = Keccak256::digest(&rlp[..len]);
 
     // resulting hash -> The last 20 bytes (40 characters) of the hash.
     let mut address = [0u8; 20];
-    address.copy_from_slice(&hash2[12..]);
-    address
+    address.copy_from_slice(&hash[12..]);
+    Address(address)
 }
 
-/// Cleans & validates the address prefix when generating a salt.
+/// Builds the `0xff ++ deployer ++ salt ++ init_code_hash` preimage hashed by `CREATE2`.
+pub(crate) fn create2_preimage(
+    deployer: &Address,
+    salt: &Salt,
+    init_code_hash: &InitCodeHash,
+) -> Vec<u8> {
+    let mut bytes: Vec<u8> = Vec::new();
+    bytes.push(0xff);
+    bytes.extend_from_slice(&deployer.0);
+    bytes.extend_from_slice(&salt.0);
+    bytes.extend_from_slice(&init_code_hash.0);
+    bytes
+}
+
+/// The longest `rlp([sender, nonce])` payload: a 1-byte list prefix, a 21-byte address and a 9-byte nonce.
+pub(crate) const RLP_CREATE_MAX_LEN: usize = 31;
+
+/// RLP encodes the `[sender, nonce]` list hashed by `CREATE` into `buf`.
 ///
 /// # Returns
 ///
-/// A sanitized version of the prefix string.
-fn sanitize_prefix(prefix: &str) -> Result<String, Create3GenerateSaltError> {
-    let prefix = prefix.trim();
+/// The length of the encoding at the start of `buf`.
+pub(crate) fn rlp_encode_create(
+    sender: &Address,
+    nonce: u64,
+    buf: &mut [u8; RLP_CREATE_MAX_LEN],
+) -> usize {
+    // The nonce is encoded as a big endian integer without leading zeros. Zero is the empty string (0x80) and
+    // values below 0x80 are their own encoding.
+    let nonce_bytes = nonce.to_be_bytes();
+    let nonce_bytes = &nonce_bytes[nonce.leading_zeros() as usize / 8..];
 
-    if prefix.len() > 20 {
-        return Err(Create3GenerateSaltError::PrefixTooLong);
-    } else if !prefix.chars().all(|c| c.is_ascii_hexdigit()) {
-        return Err(Create3GenerateSaltError::PrefixNotHexEncoded);
-    }
+    buf[1] = 0x94;
+    buf[2..22].copy_from_slice(&sender.0);
+    let len = if nonce == 0 {
+        buf[22] = 0x80;
+        23
+    } else if nonce < 0x80 {
+        buf[22] = nonce as u8;
+        23
+    } else {
+        buf[22] = 0x80 + nonce_bytes.len() as u8;
+        buf[23..23 + nonce_bytes.len()].copy_from_slice(nonce_bytes);
+        23 + nonce_bytes.len()
+    };
 
-    Ok(prefix.to_lowercase())
+    // A 20-byte sender and a nonce of at most 9 bytes always fit in a short list (< 56 bytes).
+    buf[0] = 0xc0 + (len - 1) as u8;
+    len
 }
 
-/// Generates a random salt for a given deployer and prefix.
+/// Generates a random salt for a given deployer and address pattern.
 /// # Arguments
 ///
-/// * `deployer` - A byte slice representing the create3 deployer address.
-/// * `prefix` - A string representing the prefix that the resulting address should start with (without 0x).
+/// * `deployer` - The create3 deployer address.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
 ///
 /// # Returns
 ///
-/// A tuple where the first element is the string formatted generated salt, and the second element is a
-/// 32-byte array representing the digested generated salt.
+/// The generated salt, with its preimage string, the resulting address and how it was found.
 pub fn generate_salt(
-    deployer: &[u8],
-    prefix: &str,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    let mut salt_bytes = [0; 32];
-    let mut salt: String;
-    let prefix = sanitize_prefix(prefix)?;
-
-    loop {
-        salt = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(10)
-            .map(char::from)
-            .collect();
-        let vanity_addr = calc_addr(deployer, &salt.as_bytes());
-        let vanity_addr = hex::encode(&vanity_addr);
-        if vanity_addr.starts_with(&prefix) {
-            let salt_hex = hex::encode(Keccak256::digest(salt.clone()));
-            let salt_bytes_slice = hex::decode(&salt_hex).unwrap();
-            salt_bytes.copy_from_slice(&salt_bytes_slice);
-            break;
-        }
-    }
-    Ok((salt, salt_bytes))
+    deployer: &Address,
+    pattern: &str,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    let pattern = Pattern::new(pattern)?;
+    search_once(deployer, "", &pattern, 1, scheme.into(), limits)
 }
 
-/// Generates a random salt for a given deployer and prefix by using multiple threads.
+/// Generates a random salt for a given deployer and address pattern by using multiple threads.
 /// # Arguments
 ///
-/// * `deployer` - A byte slice representing the create3 deployer address.
-/// * `prefix` - A string representing the prefix that the resulting address should start with (without 0x).
-/// * `thread_count` - A u8 integer representing the number of threads to create when calculating the address.
+/// * `deployer` - The create3 deployer address.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
 ///
 /// # Returns
 ///
-/// A tuple where the first element is the string formatted generated salt, and the second element is a
-/// 32-byte array representing the digested generated salt.
+/// The generated salt, with its preimage string, the resulting address and how it was found.
 pub fn generate_salt_multithread(
-    deployer: &[u8],
-    prefix: &str,
-    thread_count: u8,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    generate_salt_prefix_multithread(deployer, "", prefix, thread_count)
+    deployer: &Address,
+    pattern: &str,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    generate_salt_prefix_multithread(deployer, "", pattern, thread_count, scheme, limits)
 }
 
-/// Generates a salt with a prefix for a given address prefix and salt.
+/// Generates a random salt for a given deployer and a pattern whose letter case must match the EIP-55 checksummed
+/// address.
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `pattern` - A case-sensitive pattern the checksummed address should match, e.g. a prefix like `DeaDBeeF`
+///   (see `Pattern`).
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
+///
+/// # Returns
 ///
+/// The generated salt, with its preimage string, the resulting address and how it was found.
+pub fn generate_salt_checksum(
+    deployer: &Address,
+    pattern: &str,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    let pattern = Pattern::new_checksum(pattern)?;
+    search_once(deployer, "", &pattern, 1, scheme.into(), limits)
+}
+
+/// Generates a random salt for a given deployer and a pattern whose letter case must match the EIP-55 checksummed
+/// address by using multiple threads.
 /// # Arguments
 ///
-/// * `deployer` - A byte slice representing the create3 deployer address.
+/// * `deployer` - The create3 deployer address.
+/// * `pattern` - A case-sensitive pattern the checksummed address should match, e.g. a prefix like `DeaDBeeF`
+///   (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
+///
+/// # Returns
+///
+/// The generated salt, with its preimage string, the resulting address and how it was found.
+pub fn generate_salt_checksum_multithread(
+    deployer: &Address,
+    pattern: &str,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    let pattern = Pattern::new_checksum(pattern)?;
+    search_once(deployer, "", &pattern, thread_count, scheme.into(), limits)
+}
+
+/// Generates a salt with a prefix for a given address pattern and salt.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
 /// * `salt_prefix` - A string representing the prefix to append to the generated salt.
-/// * `prefix` - A hex encoded string representing the prefix that the resulting address should start with (without 0x).
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
 ///
 /// # Returns
 ///
-/// A tuple where the first element is the string formatted generated salt, and the second element is a
-/// 32-byte array representing the digested generated salt.
+/// The generated salt, with its preimage string, the resulting address and how it was found.
 pub fn generate_salt_prefix(
-    deployer: &[u8],
+    deployer: &Address,
     salt_prefix: &str,
-    prefix: &str,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    let mut salt_bytes = [0; 32];
-    let mut salt: String;
-    let prefix = sanitize_prefix(prefix)?;
-
-    loop {
-        salt = rand::thread_rng()
-            .sample_iter(&Alphanumeric)
-            .take(7)
-            .map(char::from)
-            .collect();
-        salt = salt_prefix.to_owned() + &salt;
-        let vanity_addr = calc_addr(deployer, &salt.as_bytes());
-        let vanity_addr = hex::encode(&vanity_addr);
-        if vanity_addr.starts_with(&prefix) {
-            let salt_hex = hex::encode(Keccak256::digest(salt.clone()));
-            let salt_bytes_slice = hex::decode(&salt_hex).unwrap();
-            salt_bytes.copy_from_slice(&salt_bytes_slice);
-            break;
-        }
-    }
-    Ok((salt, salt_bytes))
+    pattern: &str,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    let pattern = Pattern::new(pattern)?;
+    search_once(deployer, salt_prefix, &pattern, 1, scheme.into(), limits)
 }
 
-/// Generates a salt with a prefix for a given address prefix and salt.
+/// Generates a salt with a prefix for a given address pattern and salt.
 ///
 /// # Arguments
 ///
-/// * `deployer` - A byte slice representing the create3 deployer address.
+/// * `deployer` - The create3 deployer address.
 /// * `salt_prefix` - A string representing the prefix to append to the generated salt.
-/// * `prefix` - A hex encoded string representing the prefix that the resulting address should start with (without 0x).
-/// * `thread_count` - A u8 integer representing the number of threads to create when calculating the address.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error. `SearchLimits::default()` never
+///   gives up.
 ///
 /// # Returns
 ///
-/// A tuple where the first element is the string formatted generated salt, and the second element is a
-/// 32-byte array representing the digested generated salt.
+/// The generated salt, with its preimage string, the resulting address and how it was found.
 pub fn generate_salt_prefix_multithread(
-    deployer: &[u8],
+    deployer: &Address,
     salt_prefix: &str,
-    prefix: &str,
-    thread_count: u8,
-) -> Result<(String, [u8; 32]), Create3GenerateSaltError> {
-    // Create locks
-    let lock: Arc<RwLock<(String, [u8; 32])>> = Arc::new(RwLock::new(("".to_owned(), [0; 32])));
-    let mut threads: Vec<thread::JoinHandle<()>> = Vec::new();
-
-    let prefix = sanitize_prefix(prefix)?;
-
-    // Creates threads
-    for _ in 0..thread_count {
-        let p = prefix.to_owned();
-        let d = deployer.clone().to_owned();
-        let sp = salt_prefix.to_owned();
-
-        let lock = lock.clone();
-        let handle = thread::spawn(move || {
-            let mut salt: String;
-            let mut salt_bytes = [0; 32];
-
-            loop {
-                // Generates a vanity address (potentially extensive calculation)
-                salt = rand::thread_rng()
-                    .sample_iter(&Alphanumeric)
-                    .take(7)
-                    .map(char::from)
-                    .collect();
-                salt = sp.to_owned() + &salt;
-                let vanity_addr = calc_addr(&d, &salt.as_bytes());
-                let vanity_addr = hex::encode(&vanity_addr);
-
-                // Get read lock, otherwise break because some other thread acquired the write lock
-                let Ok(read_lock) = lock.try_read() else {
-                    break;
-                };
-
-                // If the length of the vanity address is greater than 0, it has already been written (we can stop)
-                if read_lock.0.len() > 0 {
-                    break;
-                }
-
-                // If the vanity address doesn't match, then continue
-                if !vanity_addr.starts_with(&p) {
-                    continue;
-                }
-
-                // Drop read lock and attempt to acquire write lock
-                drop(read_lock);
-                let mut write_lock = lock.write().unwrap();
-
-                // Write to lock
-                let salt_hex = hex::encode(Keccak256::digest(salt.clone()));
-                let salt_bytes_slice = hex::decode(&salt_hex).unwrap();
-                salt_bytes.copy_from_slice(&salt_bytes_slice);
-                *write_lock = (salt, salt_bytes);
-
-                // Clean up and finish
-                drop(write_lock);
-                break;
-            }
-        });
-
-        threads.push(handle);
-    }
-
-    // Ensure all threads wrap up
-    for t in threads {
-        t.join().unwrap();
-    }
-
-    let read_lock: std::sync::RwLockReadGuard<'_, (String, [u8; 32])> = lock.read().unwrap();
-    Ok((read_lock.0.clone(), read_lock.1.clone()))
+    pattern: &str,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    let pattern = Pattern::new(pattern)?;
+    search_once(
+        deployer,
+        salt_prefix,
+        &pattern,
+        thread_count,
+        scheme.into(),
+        limits,
+    )
 }
 
 #[cfg(test)]
 mod tests {
-    use std::vec;
+    use std::{
+        sync::{Arc, Mutex},
+        thread,
+        time::{Duration, Instant},
+        vec,
+    };
 
     use crate::{
-        calc_addr, calc_addr_with_bytes, generate_salt, generate_salt_multithread,
-        generate_salt_prefix, generate_salt_prefix_multithread, Create3GenerateSaltError,
+        calc_addr, calc_addr_sender_bound, calc_addr_with_bytes, calc_addr_with_bytes_sender_bound,
+        calc_addr_with_encoding, calc_create2_addr, calc_create_addr, generate_salt,
+        generate_salt_checksum, generate_salt_checksum_multithread, generate_salt_multithread,
+        generate_salt_prefix, generate_salt_prefix_multithread, rlp_encode_create, Address,
+        CancellationToken, Create3GenerateSaltError, Create3Variant, DeployMode, InitCodeHash,
+        MinedSalt, Pattern, ProgressHook, Salt, SaltEncoding, SaltScheme, SearchLimits,
+        SenderBinding, RLP_CREATE_MAX_LEN,
     };
     use sha3::{Digest, Keccak256};
 
     #[test]
     fn should_calculate_correctly_with_given_salt_string() {
-        let deployer: &Vec<u8> = &hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
+        let deployer: &Address = &"0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap();
 
         // Answers were generated with the Solady CREATE3 library
         // https://github.com/Vectorized/solady/blob/main/src/utils/CREATE3.sol
@@ -299,15 +467,16 @@ mod tests {
         ];
 
         for (salt, answer) in correct_answers.iter() {
-            let addr: [u8; 20] = calc_addr(deployer, salt.as_bytes());
+            let addr: Address = calc_addr(deployer, salt.as_bytes(), Create3Variant::Solady);
             let addr_str = hex::encode(addr);
             assert_eq!(addr_str, answer.to_lowercase());
+            assert_eq!(addr.to_checksum(), format!("0x{}", answer));
         }
     }
 
     #[test]
     fn should_calculate_correctly_with_given_salt() {
-        let deployer = &hex::decode("d8b934580fcE35a11B58C6D73aDeE468a2833fa8").unwrap();
+        let deployer: &Address = &"d8b934580fcE35a11B58C6D73aDeE468a2833fa8".parse().unwrap();
 
         // Answers were generated with the Solady CREATE3 library
         // https://github.com/Vectorized/solady/blob/main/src/utils/CREATE3.sol
@@ -331,27 +500,138 @@ mod tests {
         ];
 
         for (salt, answer) in correct_answers.iter() {
-            let salt: [u8; 32] = hex::decode(*salt).unwrap()[0..32].try_into().unwrap();
-            let addr: [u8; 20] = calc_addr_with_bytes(deployer, &salt);
+            let salt: Salt = salt.parse().unwrap();
+            let addr: Address = calc_addr_with_bytes(deployer, &salt, Create3Variant::Solady);
             let addr_str = hex::encode(addr);
             assert_eq!(addr_str, answer.to_lowercase());
         }
     }
 
+    #[test]
+    fn should_calculate_with_the_variant_proxy_hash() {
+        let deployer: &Address = &"d8b934580fcE35a11B58C6D73aDeE468a2833fa8".parse().unwrap();
+        let salt = Salt([42u8; 32]);
+        let solady = calc_addr_with_bytes(deployer, &salt, Create3Variant::Solady);
+
+        let proxy = hex::decode("67363d3d37363d34f03d5260086018f3").unwrap();
+        let runs = [
+            Create3Variant::Solmate,
+            Create3Variant::ZeframLou,
+            Create3Variant::Sequence,
+            Create3Variant::CreateX,
+            Create3Variant::from_proxy_init_code(&proxy),
+        ];
+        for run in runs.iter() {
+            assert_eq!(calc_addr_with_bytes(deployer, &salt, *run), solady);
+        }
+
+        let custom = Create3Variant::Custom {
+            proxy_init_code_hash: InitCodeHash([1u8; 32]),
+        };
+        assert_ne!(calc_addr_with_bytes(deployer, &salt, custom), solady);
+    }
+
+    #[test]
+    fn should_calculate_create_addr_with_given_nonce() {
+        let sender: &Address = &"6ac7ea33f8831ea9dcc53393aaa88b25a785dbf0".parse().unwrap();
+
+        // https://ethereum.stackexchange.com/a/761/66849
+        let correct_answers: Vec<(u64, &str)> = vec![
+            (0, "cd234a471b72ba2f1ccf0a70fcaba648a5eecd8d"),
+            (1, "343c43a37d37dff08ae8c4a11544c718abb4fcf8"),
+            (2, "f778b86fa74e846c4f0a1fbd1335fe81c00a0c91"),
+            (3, "fffd933a0bc612844eaf0c6fe3e5b8e9b6c1d19c"),
+        ];
+
+        for (nonce, answer) in correct_answers.iter() {
+            assert_eq!(hex::encode(calc_create_addr(sender, *nonce)), *answer);
+        }
+    }
+
+    #[test]
+    fn should_rlp_encode_nonce_boundaries() {
+        let sender = Address([0x11u8; 20]);
+        let runs = [
+            (0, "d6", "80"),
+            (0x7f, "d6", "7f"),
+            (0x80, "d7", "8180"),
+            (0xff, "d7", "81ff"),
+            (0x100, "d8", "820100"),
+            (u64::MAX, "de", "88ffffffffffffffff"),
+        ];
+
+        for (nonce, list_prefix, nonce_rlp) in runs.iter() {
+            let mut buf = [0u8; RLP_CREATE_MAX_LEN];
+            let len = rlp_encode_create(&sender, *nonce, &mut buf);
+            let encoded = &buf[..len];
+            let expected = format!("{}94{}{}", list_prefix, hex::encode(sender), nonce_rlp);
+            assert_eq!(hex::encode(encoded), expected);
+
+            let hash = Keccak256::digest(encoded);
+            assert_eq!(calc_create_addr(&sender, *nonce).0, hash.as_slice()[12..]);
+        }
+    }
+
+    #[test]
+    fn should_calculate_create2_addr_with_given_init_code() {
+        // https://eips.ethereum.org/EIPS/eip-1014#examples
+        let correct_answers: Vec<(&str, &str, &str, &str)> = vec![
+            (
+                "0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "4D1A2e2bB4F88F0250f26Ffff098B0b30B26BF38",
+            ),
+            (
+                "deadbeef00000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "00",
+                "B928f69Bb1D91Cd65274e3c79d8986362984fDA3",
+            ),
+            (
+                "00000000000000000000000000000000deadbeef",
+                "00000000000000000000000000000000000000000000000000000000cafebabe",
+                "deadbeef",
+                "60f3f640a8508fC6a86d45DF051962668E1e8AC7",
+            ),
+            (
+                "0000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "",
+                "E33C0C7F7df4809055C3ebA6c09CFe4BaF1BD9e0",
+            ),
+        ];
+
+        for (deployer, salt, init_code, answer) in correct_answers.iter() {
+            let deployer: Address = deployer.parse().unwrap();
+            let salt: Salt = salt.parse().unwrap();
+            let init_code_hash =
+                InitCodeHash(Keccak256::digest(hex::decode(init_code).unwrap()).into());
+            let addr = calc_create2_addr(&deployer, &salt, &init_code_hash);
+            assert_eq!(hex::encode(addr), answer.to_lowercase());
+        }
+    }
+
     #[test]
     fn should_generate_with_prefix() {
-        let deployer: &Vec<u8> = &hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abc", "123", "789", "DeF"];
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = ["0", "00", "000", "abc", "123", "789", "DeF"];
 
         for run in runs.iter() {
-            let salt = generate_salt(deployer, run).unwrap();
+            let salt = generate_salt(
+                deployer,
+                run,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
 
             /* NOTE:
              * This essentially repeats the code in generate_salt. Could be useful for future changes of the function.
              * Is there a better way of testing this?
              */
-            let addr: [u8; 20] = calc_addr_with_bytes(deployer, &salt.1);
-            let addr_string = calc_addr(deployer, salt.0.as_bytes());
+            let addr: Address = calc_addr_with_bytes(deployer, &salt.salt, Create3Variant::Solady);
+            let addr_string = calc_addr(deployer, salt.preimage.as_bytes(), Create3Variant::Solady);
 
             assert_eq!(addr, addr_string);
             assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
@@ -360,96 +640,569 @@ mod tests {
 
     #[test]
     fn should_generate_multithread_with_prefix() {
-        let deployer: &Vec<u8> = &hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abcd", "123", "789", "DeF"];
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        // A thread count of 0 runs one thread per available core.
+        let runs = [
+            ("0", 0),
+            ("00", 6),
+            ("000", 0),
+            ("abcd", 6),
+            ("123", 2),
+            ("789", 6),
+            ("DeF", 0),
+        ];
+
+        for (run, thread_count) in runs.iter() {
+            let salt = generate_salt_multithread(
+                deployer,
+                run,
+                *thread_count,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            let addr: Address = calc_addr_with_bytes(deployer, &salt.salt, Create3Variant::Solady);
+
+            assert_eq!(
+                calc_addr(deployer, salt.preimage.as_bytes(), Create3Variant::Solady),
+                addr
+            );
+            assert_eq!(salt.address, addr);
+            assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn should_generate_create2_with_prefix() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let mode = DeployMode::Create2 {
+            init_code_hash: InitCodeHash(Keccak256::digest(b"init code").into()),
+        };
+        let runs = ["0", "00", "abc", "DeF"];
 
         for run in runs.iter() {
-            let salt = generate_salt_multithread(deployer, run, 6).unwrap();
-            let addr: [u8; 20] = calc_addr_with_bytes(deployer, &salt.1);
+            let MinedSalt {
+                preimage: salt,
+                salt: digested_salt,
+                ..
+            } = generate_salt_multithread(deployer, run, 6, mode, &SearchLimits::default())
+                .unwrap();
+            let addr = mode.calc_addr(deployer, salt.as_bytes());
 
-            assert_eq!(calc_addr(deployer, salt.0.as_bytes()), addr);
+            assert_eq!(mode.calc_addr_with_bytes(deployer, &digested_salt), addr);
+            assert_ne!(
+                calc_addr_with_bytes(deployer, &digested_salt, Create3Variant::Solady),
+                addr
+            );
             assert!(hex::encode(addr).starts_with(&run.to_lowercase()));
         }
     }
 
+    #[test]
+    fn should_generate_with_checksum_prefix() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = ["0", "a", "A", "dE", "Be", "12"];
+
+        for run in runs.iter() {
+            let MinedSalt {
+                preimage: salt,
+                salt: digested_salt,
+                ..
+            } = generate_salt_checksum(
+                deployer,
+                run,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            let addr = calc_addr_with_bytes(deployer, &digested_salt, Create3Variant::Solady);
+
+            assert_eq!(
+                calc_addr(deployer, salt.as_bytes(), Create3Variant::Solady),
+                addr
+            );
+            assert!(addr.to_checksum()[2..].starts_with(run));
+        }
+    }
+
+    #[test]
+    fn should_generate_multithread_with_checksum_prefix() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = ["De", "bE", "c0"];
+
+        for run in runs.iter() {
+            let MinedSalt {
+                salt: digested_salt,
+                ..
+            } = generate_salt_checksum_multithread(
+                deployer,
+                run,
+                6,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            let addr = calc_addr_with_bytes(deployer, &digested_salt, Create3Variant::Solady);
+            assert!(addr.to_string()[2..].starts_with(run));
+        }
+    }
+
+    #[test]
+    fn should_generate_with_pattern() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = ["0x12", "*ab", "*c0*", "a?b", "?0*1"];
+
+        for run in runs.iter() {
+            let MinedSalt {
+                salt: digested_salt,
+                ..
+            } = generate_salt_multithread(
+                deployer,
+                run,
+                6,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            let addr = calc_addr_with_bytes(deployer, &digested_salt, Create3Variant::Solady);
+            assert!(Pattern::new(run).unwrap().matches(&addr), "{}", run);
+        }
+
+        let MinedSalt {
+            salt: digested_salt,
+            ..
+        } = generate_salt_checksum(
+            deployer,
+            "*Ab",
+            Create3Variant::Solady,
+            &SearchLimits::default(),
+        )
+        .unwrap();
+        let addr = calc_addr_with_bytes(deployer, &digested_salt, Create3Variant::Solady);
+        assert!(addr.to_checksum().ends_with("Ab"));
+    }
+
+    #[test]
+    fn should_describe_the_mined_salt() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let scheme = SaltScheme::new(Create3Variant::Sequence, SaltEncoding::RightPadded);
+        let mined = generate_salt_checksum_multithread(
+            deployer,
+            "*aB",
+            2,
+            scheme,
+            &SearchLimits::default(),
+        )
+        .unwrap();
+
+        assert_eq!(mined.deployer, *deployer);
+        assert_eq!(mined.scheme, scheme);
+        assert_eq!((mined.pattern.as_str(), mined.checksum), ("*aB", true));
+        assert_eq!(scheme.encoding.encode(&mined.preimage), Ok(mined.salt));
+        assert_eq!(
+            scheme.calc_addr_with_bytes(deployer, &mined.salt),
+            mined.address
+        );
+        assert!(mined.address.to_checksum().ends_with("aB"));
+        assert!(mined.attempts >= 1);
+    }
+
     #[test]
     fn should_generate_with_empty_prefix() {
-        let deployer: &Vec<u8> = &hex::decode("0fC5025C764cE34df352757e82f7B5c4Df39A836").unwrap();
-        assert!(generate_salt(deployer, "").is_ok());
+        let deployer: &Address = &"0fC5025C764cE34df352757e82f7B5c4Df39A836".parse().unwrap();
+        assert!(generate_salt(
+            deployer,
+            "",
+            Create3Variant::Solady,
+            &SearchLimits::default()
+        )
+        .is_ok());
     }
 
     #[test]
     fn should_generate_with_salt_prefix() {
-        let deployer: &Vec<u8> = &hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abc", "123", "789", "DeF"];
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = ["0", "00", "000", "abc", "123", "789", "DeF"];
         let salt_prefix = "testpfx_";
         for run in runs.iter() {
-            let (salt, digested_salt) = generate_salt_prefix(deployer, salt_prefix, run).unwrap();
+            let MinedSalt {
+                preimage: salt,
+                salt: digested_salt,
+                ..
+            } = generate_salt_prefix(
+                deployer,
+                salt_prefix,
+                run,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
             assert!(salt.starts_with(&salt_prefix.to_lowercase()));
-            assert_eq!(Keccak256::digest(salt).as_slice()[0..32], digested_salt);
-            assert!(hex::encode(calc_addr_with_bytes(deployer, &digested_salt))
-                .starts_with(&run.to_lowercase()));
+            assert_eq!(Keccak256::digest(salt).as_slice()[0..32], digested_salt.0);
+            assert!(hex::encode(calc_addr_with_bytes(
+                deployer,
+                &digested_salt,
+                Create3Variant::Solady
+            ))
+            .starts_with(&run.to_lowercase()));
         }
     }
 
     #[test]
     fn should_generate_multithread_with_salt_prefix() {
-        let deployer: &Vec<u8> = &hex::decode("5e17b14ADd6c386305A32928F985b29bbA34Eff5").unwrap();
-        let runs = vec!["0", "00", "000", "abc", "123", "789", "DeF"];
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = ["0", "00", "000", "abc", "123", "789", "DeF"];
         let salt_prefix = "testpfx_";
         for run in runs.iter() {
-            let (salt, digested_salt) =
-                generate_salt_prefix_multithread(deployer, salt_prefix, run, 6).unwrap();
+            let MinedSalt {
+                preimage: salt,
+                salt: digested_salt,
+                ..
+            } = generate_salt_prefix_multithread(
+                deployer,
+                salt_prefix,
+                run,
+                6,
+                Create3Variant::Solady,
+                &SearchLimits::default(),
+            )
+            .unwrap();
             assert!(salt.starts_with(&salt_prefix.to_lowercase()));
-            assert_eq!(Keccak256::digest(salt).as_slice()[0..32], digested_salt);
-            assert!(hex::encode(calc_addr_with_bytes(deployer, &digested_salt))
-                .starts_with(&run.to_lowercase()));
+            assert_eq!(Keccak256::digest(salt).as_slice()[0..32], digested_salt.0);
+            assert!(hex::encode(calc_addr_with_bytes(
+                deployer,
+                &digested_salt,
+                Create3Variant::Solady
+            ))
+            .starts_with(&run.to_lowercase()));
+        }
+    }
+
+    #[test]
+    fn should_generate_with_salt_encoding() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs = [
+            (SaltEncoding::AbiEncode, "testpfx_"),
+            (SaltEncoding::RightPadded, "testpfx_"),
+            (SaltEncoding::Uint256, "42"),
+            (SaltEncoding::Raw, "0xc0ffee"),
+        ];
+        for (encoding, salt_prefix) in runs.iter() {
+            let scheme = SaltScheme::new(Create3Variant::Solady, *encoding);
+            let MinedSalt {
+                preimage: salt,
+                salt: encoded_salt,
+                ..
+            } = generate_salt_prefix_multithread(
+                deployer,
+                salt_prefix,
+                "ab",
+                6,
+                scheme,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            assert!(salt.starts_with(salt_prefix));
+            assert_eq!(encoding.encode(&salt), Ok(encoded_salt));
+            assert_eq!(
+                calc_addr_with_encoding(deployer, &salt, *encoding, Create3Variant::Solady),
+                Ok(calc_addr_with_bytes(
+                    deployer,
+                    &encoded_salt,
+                    Create3Variant::Solady
+                ))
+            );
+            assert!(hex::encode(scheme.calc_addr(deployer, &salt).unwrap()).starts_with("ab"));
+        }
+
+        let scheme = SaltScheme::new(Create3Variant::Solady, SaltEncoding::RightPadded);
+        let MinedSalt {
+            preimage: salt,
+            salt: encoded_salt,
+            ..
+        } = generate_salt(deployer, "a", scheme, &SearchLimits::default()).unwrap();
+        assert_eq!(encoded_salt.0[..salt.len()], *salt.as_bytes());
+    }
+
+    #[test]
+    fn should_calculate_sender_bound_addr() {
+        let deployer: &Address = &"9fBB3DF7C40Da2e5A0dE984fFE2CCB7C47cd0ABf".parse().unwrap();
+        let sender: Address = "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        let salt = Salt(Keccak256::digest(b"a").into());
+
+        // keccak256(abi.encodePacked(msg.sender, salt))
+        let mut packed = sender.0.to_vec();
+        packed.extend_from_slice(&salt.0);
+        let bound_salt = Salt(Keccak256::digest(&packed).into());
+
+        let binding = SenderBinding::Packed(sender);
+        let addr = calc_addr_with_bytes(deployer, &bound_salt, Create3Variant::ZeframLou);
+        assert_eq!(
+            calc_addr_with_bytes_sender_bound(deployer, &binding, &salt, Create3Variant::ZeframLou),
+            addr
+        );
+        assert_eq!(
+            calc_addr_sender_bound(deployer, &binding, b"a", Create3Variant::ZeframLou),
+            addr
+        );
+        assert_ne!(calc_addr(deployer, b"a", Create3Variant::ZeframLou), addr);
+    }
+
+    #[test]
+    fn should_generate_multithread_with_sender_binding() {
+        let deployer: &Address = &"9fBB3DF7C40Da2e5A0dE984fFE2CCB7C47cd0ABf".parse().unwrap();
+        let sender: Address = "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        let runs = [
+            SenderBinding::Packed(sender),
+            SenderBinding::AbiEncode(sender),
+        ];
+        let salt_prefix = "testpfx_";
+
+        for binding in runs.iter() {
+            let scheme = SaltScheme::from(Create3Variant::ZeframLou).with_sender(*binding);
+            let MinedSalt {
+                preimage: salt,
+                salt: digested_salt,
+                ..
+            } = generate_salt_prefix_multithread(
+                deployer,
+                salt_prefix,
+                "abc",
+                6,
+                scheme,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            assert_eq!(Keccak256::digest(&salt).as_slice()[0..32], digested_salt.0);
+
+            let addr = calc_addr_sender_bound(
+                deployer,
+                binding,
+                salt.as_bytes(),
+                Create3Variant::ZeframLou,
+            );
+            assert!(hex::encode(addr).starts_with("abc"));
+        }
+    }
+
+    #[test]
+    fn generate_salt_prefix_should_error_if_salt_prefix_cannot_be_encoded() {
+        let deployer: &Address = &"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let runs = [
+            (SaltEncoding::RightPadded, "a_salt_prefix_that_is_too_long"),
+            (SaltEncoding::Uint256, "testpfx_"),
+            (SaltEncoding::Raw, "testpfx_"),
+        ];
+        for (encoding, salt_prefix) in runs.iter() {
+            let scheme = SaltScheme::new(Create3Variant::Solady, *encoding);
+            assert_eq!(
+                generate_salt_prefix(deployer, salt_prefix, "0", scheme, &SearchLimits::default()),
+                Err(Create3GenerateSaltError::InvalidSaltPrefix)
+            );
+            assert_eq!(
+                generate_salt_prefix_multithread(
+                    deployer,
+                    salt_prefix,
+                    "0",
+                    2,
+                    scheme,
+                    &SearchLimits::default()
+                ),
+                Err(Create3GenerateSaltError::InvalidSaltPrefix)
+            );
         }
     }
 
     #[test]
     fn generate_salt_should_error_if_prefix_is_greater_than_20_bytes() {
-        let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
+        let deployer: &Address = &"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
         let prefix = "0x00000000000000000000000000000000000000000";
         assert_eq!(
-            generate_salt(deployer, prefix),
-            Err(Create3GenerateSaltError::PrefixTooLong)
+            generate_salt(
+                deployer,
+                prefix,
+                Create3Variant::Solady,
+                &SearchLimits::default()
+            ),
+            Err(Create3GenerateSaltError::PatternTooLong)
         );
     }
 
     #[test]
     fn generate_salt_should_error_if_prefix_is_not_hex_encoded() {
-        let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
-        let runs = vec!["hey", "abcg", "0x123", "Ab45[", "lightning mcqueen"];
-        for run in runs.iter() {
+        let deployer: &Address = &"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let runs = [
+            ("hey", 0, 'h'),
+            ("abcg", 3, 'g'),
+            ("0x0x123", 3, 'x'),
+            ("Ab45[", 4, '['),
+            ("lightning mcqueen", 0, 'l'),
+        ];
+        for (run, index, char) in runs {
             assert_eq!(
-                generate_salt(deployer, run),
-                Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+                generate_salt(
+                    deployer,
+                    run,
+                    Create3Variant::Solady,
+                    &SearchLimits::default()
+                ),
+                Err(Create3GenerateSaltError::InvalidPatternChar { index, char })
             );
         }
     }
 
     #[test]
     fn generate_salt_prefix_should_error_if_prefix_is_greater_than_20_bytes() {
-        let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
+        let deployer: &Address = &"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
         let salt_prefix = "";
         let prefix = "0x00000000000000000000000000000000000000000";
         assert_eq!(
-            generate_salt_prefix(deployer, salt_prefix, prefix),
-            Err(Create3GenerateSaltError::PrefixTooLong)
+            generate_salt_prefix(
+                deployer,
+                salt_prefix,
+                prefix,
+                Create3Variant::Solady,
+                &SearchLimits::default()
+            ),
+            Err(Create3GenerateSaltError::PatternTooLong)
         );
     }
 
     #[test]
     fn generate_salt_prefix_should_error_if_prefix_is_not_hex_encoded() {
-        let deployer = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045".as_bytes();
+        let deployer: &Address = &"0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
         let salt_prefix = "";
-        let runs = vec!["hey", "abcg", "0x123", "Ab45[", "lightning mcqueen"];
-        for run in runs.iter() {
+        let runs = [
+            ("hey", 0, 'h'),
+            ("abcg", 3, 'g'),
+            ("0x0x123", 3, 'x'),
+            ("Ab45[", 4, '['),
+            ("lightning mcqueen", 0, 'l'),
+        ];
+        for (run, index, char) in runs {
             assert_eq!(
-                generate_salt_prefix(deployer, salt_prefix, run),
-                Err(Create3GenerateSaltError::PrefixNotHexEncoded)
+                generate_salt_prefix(
+                    deployer,
+                    salt_prefix,
+                    run,
+                    Create3Variant::Solady,
+                    &SearchLimits::default()
+                ),
+                Err(Create3GenerateSaltError::InvalidPatternChar { index, char })
             );
         }
     }
+
+    #[test]
+    fn should_time_out_after_max_attempts_or_at_deadline() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        // 40 nibbles will not be found by any of these searches.
+        let pattern = "0".repeat(40);
+        let runs = [
+            SearchLimits::new().with_max_attempts(1000),
+            SearchLimits::new().with_timeout(Duration::from_millis(100)),
+            SearchLimits::new()
+                .with_max_attempts(u64::MAX)
+                .with_deadline(Instant::now()),
+        ];
+
+        for limits in runs.iter() {
+            let start = Instant::now();
+            assert_eq!(
+                generate_salt(deployer, &pattern, Create3Variant::Solady, limits),
+                Err(Create3GenerateSaltError::Timeout)
+            );
+            assert_eq!(
+                generate_salt_prefix_multithread(
+                    deployer,
+                    "pfx_",
+                    &pattern,
+                    6,
+                    Create3Variant::Solady,
+                    limits
+                ),
+                Err(Create3GenerateSaltError::Timeout)
+            );
+            assert!(start.elapsed() < Duration::from_secs(10));
+        }
+
+        // A salt within the attempt limit is still found.
+        let limits = SearchLimits::new().with_max_attempts(1);
+        assert!(
+            generate_salt_multithread(deployer, "", 6, Create3Variant::Solady, &limits).is_ok()
+        );
+    }
+
+    #[test]
+    fn should_stop_all_threads_when_cancelled() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let pattern = "0".repeat(40);
+        let token = CancellationToken::new();
+        let limits = SearchLimits::new().with_cancellation(token.clone());
+
+        let start = Instant::now();
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(100));
+                token.cancel();
+            });
+            assert_eq!(
+                generate_salt_checksum_multithread(
+                    deployer,
+                    &pattern,
+                    6,
+                    Create3Variant::Solady,
+                    &limits
+                ),
+                Err(Create3GenerateSaltError::Cancelled)
+            );
+        });
+        assert!(start.elapsed() < Duration::from_secs(10));
+
+        // An already cancelled token stops the search right away.
+        assert_eq!(
+            generate_salt_prefix(deployer, "", "", Create3Variant::Solady, &limits),
+            Err(Create3GenerateSaltError::Cancelled)
+        );
+    }
+
+    #[test]
+    fn should_report_progress() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let hook = {
+            let reports = reports.clone();
+            ProgressHook::new(Duration::from_millis(20), move |progress| {
+                reports.lock().unwrap().push(progress.clone())
+            })
+        };
+        let limits = SearchLimits::new()
+            .with_timeout(Duration::from_millis(500))
+            .with_progress(hook);
+
+        assert_eq!(
+            generate_salt_multithread(deployer, "ffffffff", 3, Create3Variant::Solady, &limits),
+            Err(Create3GenerateSaltError::Timeout)
+        );
+
+        let reports = reports.lock().unwrap();
+        assert!(!reports.is_empty());
+        assert!(reports
+            .windows(2)
+            .all(|pair| pair[0].attempts <= pair[1].attempts));
+        let last = reports.last().unwrap();
+        assert_eq!(last.thread_hashrates.len(), 3);
+        assert_eq!(last.expected_attempts, 16f64.powi(8));
+        assert!(last.attempts > 0 && last.eta().is_some());
+    }
 }
@@ -1,20 +1,35 @@
 use create3::{
-    calc_addr, calc_addr_with_bytes, errors::Create3GenerateSaltError, generate_salt,
-    generate_salt_prefix,
+    errors::{Create3GenerateSaltError, Create3ParseError},
+    generate_salt, generate_salt_checksum, generate_salt_prefix,
+    optimize::{generate_salt_zeros, ZeroScore},
+    raw::{generate_raw_salt_multithread, MAX_FIXED_BYTES},
+    search::{generate_salt_resumable, measure_hashrate, SearchState},
+    Address, Create3Derivation, DeployMode, Difficulty, Pattern, Progress, ProgressHook,
+    SaltEncoding, SaltScheme, SearchLimits, SenderBinding,
+};
+use signal_hook::consts::SIGINT;
+use std::{
+    io::{self, Write},
+    path::PathBuf,
+    sync::{atomic::AtomicBool, Arc},
+    time::Duration,
 };
-use std::io::{self, Write};
 
 fn main() {
     println!("\x1b[32m=========================\x1b[0m");
     println!("\x1b[32m=  CREATE3 ADDRESS TOOL  =\x1b[0m");
     println!("\x1b[32m=========================\x1b[0m");
 
-    loop {
+    'menu: loop {
         println!("\n\x1b[36mWhat would you like to do?\x1b[0m");
-        println!("\x1b[33m1. Generate CREATE3 address\x1b[0m");
-        println!("\x1b[33m2. Generate salt for prefixed address\x1b[0m");
-        println!("\x1b[33m3. Generate optimised suffix for prefixed address and salt\x1b[0m");
-        print!("\x1b[36mEnter your choice (1/2/3):\x1b[0m ");
+        println!("\x1b[33m1. Generate CREATE3/CREATE2 address\x1b[0m");
+        println!("\x1b[33m2. Generate salt for vanity address pattern\x1b[0m");
+        println!("\x1b[33m3. Generate optimised suffix for vanity address pattern and salt\x1b[0m");
+        println!("\x1b[33m4. Explain CREATE3 address derivation\x1b[0m");
+        println!("\x1b[33m5. Optimise address for zero bytes (gas efficient)\x1b[0m");
+        println!("\x1b[33m6. Resumable salt search with a checkpoint file\x1b[0m");
+        println!("\x1b[33m7. Generate raw bytes32 salt for vanity address pattern\x1b[0m");
+        print!("\x1b[36mEnter your choice (1/2/3/4/5/6/7):\x1b[0m ");
         io::stdout().flush().unwrap();
 
         let mut choice = String::new();
@@ -24,63 +39,91 @@ fn main() {
         match choice {
             "1" => {
                 let deployer = request_deployer_address();
+                let scheme = request_scheme();
 
-                print!("\x1b[36mEnter salt (utf8):\x1b[0m ");
-                io::stdout().flush().unwrap();
-                let mut salt = String::new();
-                io::stdin().read_line(&mut salt).unwrap();
-                let salt = salt.trim();
+                print!("\x1b[36mEnter salt ({}):\x1b[0m ", scheme.encoding);
+                let address = loop {
+                    io::stdout().flush().unwrap();
+                    let mut salt = String::new();
+                    io::stdin().read_line(&mut salt).unwrap();
 
-                let address = calc_addr(&deployer, salt.as_bytes());
-                println!(
-                    "\x1b[32mCREATE3 address:\x1b[0m 0x{}",
-                    hex::encode(&address)
-                );
+                    match scheme.calc_addr(&deployer, salt.trim()) {
+                        Ok(address) => break address,
+                        Err(e) => {
+                            print!("\x1b[36mInvalid salt ({}). Please enter salt:\x1b[0m ", e)
+                        }
+                    }
+                };
+                println!("\x1b[32mAddress:\x1b[0m {}", address);
                 break;
             }
             "2" => {
                 let deployer = request_deployer_address();
+                let scheme = request_scheme();
+
+                let checksum =
+                    request_confirmation("Match the checksummed letter case of the pattern?");
 
                 let salt;
                 let mut prefix;
+                let limits = SearchLimits::new()
+                    .with_cancellation(stop_on_ctrl_c().into())
+                    .with_progress(status_line());
 
-                print!("\x1b[36mEnter prefix (without '0x' prefix):\x1b[0m ");
+                print!("\x1b[36mEnter address pattern (hex prefix, '?' for any nibble, '*' for any run, e.g. dead, *beef, 00??*cafe):\x1b[0m ");
                 loop {
                     io::stdout().flush().unwrap();
                     prefix = String::new();
                     io::stdin().read_line(&mut prefix).unwrap();
                     prefix = prefix.trim().to_owned();
 
-                    match generate_salt(&deployer, &prefix) {
+                    if !confirm_difficulty(&deployer, "", &prefix, checksum, 1, scheme) {
+                        print!("\x1b[36mPlease enter pattern:\x1b[0m ");
+                        continue;
+                    }
+
+                    let generated = if checksum {
+                        generate_salt_checksum(&deployer, &prefix, scheme, &limits)
+                    } else {
+                        generate_salt(&deployer, &prefix, scheme, &limits)
+                    };
+                    clear_status_line();
+
+                    match generated {
                         Ok(s) => {
                             salt = s;
                             break;
                         }
-                        Err(Create3GenerateSaltError::PrefixNotHexEncoded) => {
-                            print!("\x1b[36mInput was not hex encoded. Please enter prefix (without '0x' prefix):\x1b[0m ")
+                        Err(Create3GenerateSaltError::InvalidPatternChar { index, char }) => {
+                            print!("\x1b[36mPattern may only contain hex digits, '?' and '*', found '{}' at position {}. Please enter pattern:\x1b[0m ", char, index)
+                        }
+                        Err(Create3GenerateSaltError::PatternTooLong) => {
+                            print!("\x1b[36mPattern was too long (over 40 nibbles). Please enter pattern:\x1b[0m ")
+                        }
+                        Err(Create3GenerateSaltError::InvalidSaltPrefix) => {
+                            println!("\x1b[31mThe salt prefix cannot be used with the {} encoding.\x1b[0m", scheme.encoding);
+                            continue 'menu;
                         }
-                        Err(Create3GenerateSaltError::PrefixTooLong) => {
-                            print!("\x1b[36mPrefix was too long (over 20 characters). Please enter prefix (without '0x' prefix):\x1b[0m ")
+                        Err(e) => {
+                            println!("\x1b[31m{}\x1b[0m", e);
+                            continue 'menu;
                         }
                     }
                 }
 
+                println!("\x1b[32mVanity address:\x1b[0m {}", salt.address);
+                println!("\x1b[32mSalt string:\x1b[0m {}", salt.preimage);
                 println!(
-                    "\x1b[32mVanity address:\x1b[0m 0x{}",
-                    hex::encode(calc_addr_with_bytes(&deployer, &salt.1))
-                );
-                println!("\x1b[32mSalt string:\x1b[0m {}", salt.0);
-                println!(
-                    "\x1b[32mHashed salt for prefix {}:\x1b[0m 0x{}",
-                    prefix,
-                    hex::encode(salt.1)
+                    "\x1b[32mEncoded salt for pattern {}:\x1b[0m {}",
+                    prefix, salt.salt
                 );
                 break;
             }
             "3" => {
                 let deployer = request_deployer_address();
+                let scheme = request_scheme();
 
-                print!("\x1b[36mEnter salt prefix (utf8):\x1b[0m ");
+                print!("\x1b[36mEnter salt prefix ({}):\x1b[0m ", scheme.encoding);
                 io::stdout().flush().unwrap();
                 let mut salt_prefix = String::new();
                 io::stdin().read_line(&mut salt_prefix).unwrap();
@@ -88,42 +131,351 @@ fn main() {
 
                 let mut prefix;
                 let generated;
-                let vanity_addr;
+                let limits = SearchLimits::new()
+                    .with_cancellation(stop_on_ctrl_c().into())
+                    .with_progress(status_line());
 
-                print!("\x1b[36mEnter address prefix (without '0x' prefix):\x1b[0m ");
+                print!("\x1b[36mEnter address pattern (hex prefix, '?' for any nibble, '*' for any run, e.g. dead, *beef, 00??*cafe):\x1b[0m ");
                 loop {
                     io::stdout().flush().unwrap();
                     prefix = String::new();
                     io::stdin().read_line(&mut prefix).unwrap();
                     prefix = prefix.trim().to_owned();
 
-                    match generate_salt_prefix(&deployer, salt_prefix, &prefix) {
+                    if !confirm_difficulty(&deployer, salt_prefix, &prefix, false, 1, scheme) {
+                        print!("\x1b[36mPlease enter pattern:\x1b[0m ");
+                        continue;
+                    }
+
+                    let result =
+                        generate_salt_prefix(&deployer, salt_prefix, &prefix, scheme, &limits);
+                    clear_status_line();
+
+                    match result {
                         Ok(s) => {
                             generated = s;
-                            vanity_addr = calc_addr_with_bytes(&deployer, &generated.1);
                             break;
                         }
-                        Err(Create3GenerateSaltError::PrefixNotHexEncoded) => {
-                            print!("\x1b[36mInput was not hex encoded. Please enter prefix (without '0x' prefix):\x1b[0m ")
+                        Err(Create3GenerateSaltError::InvalidPatternChar { index, char }) => {
+                            print!("\x1b[36mPattern may only contain hex digits, '?' and '*', found '{}' at position {}. Please enter pattern:\x1b[0m ", char, index)
+                        }
+                        Err(Create3GenerateSaltError::PatternTooLong) => {
+                            print!("\x1b[36mPattern was too long (over 40 nibbles). Please enter pattern:\x1b[0m ")
+                        }
+                        Err(Create3GenerateSaltError::InvalidSaltPrefix) => {
+                            println!("\x1b[31mThe salt prefix cannot be used with the {} encoding.\x1b[0m", scheme.encoding);
+                            continue 'menu;
                         }
-                        Err(Create3GenerateSaltError::PrefixTooLong) => {
-                            print!("\x1b[36mPrefix was too long (over 20 characters). Please enter prefix (without '0x' prefix):\x1b[0m ")
+                        Err(e) => {
+                            println!("\x1b[31m{}\x1b[0m", e);
+                            continue 'menu;
                         }
                     }
                 }
 
+                println!("\x1b[32mVanity address:\x1b[0m {}", generated.address);
                 println!(
-                    "\x1b[32mVanity address:\x1b[0m 0x{}",
-                    hex::encode(vanity_addr)
+                    "\x1b[32mSalt string for prefix {}:\x1b[0m {}",
+                    salt_prefix, generated.preimage
                 );
+                println!("\x1b[32mEncoded salt :\x1b[0m {}", generated.salt);
+                break;
+            }
+            "4" | "explain" => {
+                let deployer = request_deployer_address();
+                let DeployMode::Create3(variant) = request_mode() else {
+                    println!("\x1b[31mOnly CREATE3 factories can be explained.\x1b[0m");
+                    continue;
+                };
+
+                let encoding = request_encoding();
+
+                print!("\x1b[36mEnter salt ({}):\x1b[0m ", encoding);
+                let salt = loop {
+                    io::stdout().flush().unwrap();
+                    let mut salt = String::new();
+                    io::stdin().read_line(&mut salt).unwrap();
+
+                    match encoding.encode(salt.trim()) {
+                        Ok(salt) => break salt,
+                        Err(e) => {
+                            print!("\x1b[36mInvalid salt ({}). Please enter salt:\x1b[0m ", e)
+                        }
+                    }
+                };
+
+                let salt = match request_sender_binding() {
+                    Some(sender) => {
+                        let bound = sender.bind(&salt);
+                        println!(
+                            "\x1b[32m0. Salt passed to the factory ({}):\x1b[0m {}",
+                            sender, salt
+                        );
+                        bound
+                    }
+                    None => salt,
+                };
+
+                let derivation = Create3Derivation::new(&deployer, &salt, variant);
+                println!("\x1b[32m1. Salt:\x1b[0m {}", derivation.salt);
                 println!(
-                    "\x1b[32mSalt string for prefix {}:\x1b[0m {}",
-                    salt_prefix, generated.0
+                    "\x1b[32m2. Proxy init code hash ({}):\x1b[0m {}",
+                    derivation.variant, derivation.proxy_init_code_hash
+                );
+                println!(
+                    "\x1b[32m3. CREATE2 preimage (0xff ++ deployer ++ salt ++ hash):\x1b[0m 0x{}",
+                    hex::encode(&derivation.create2_preimage)
                 );
                 println!(
-                    "\x1b[32mHashed salt :\x1b[0m 0x{}",
-                    hex::encode(&generated.1)
+                    "\x1b[32m4. Proxy address (keccak256(preimage)[12:]):\x1b[0m {}",
+                    derivation.proxy
                 );
+                println!(
+                    "\x1b[32m5. RLP payload (rlp([proxy, {}])):\x1b[0m 0x{}",
+                    derivation.proxy_nonce,
+                    hex::encode(&derivation.rlp_payload)
+                );
+                println!(
+                    "\x1b[32m6. CREATE3 address (keccak256(payload)[12:]):\x1b[0m {}",
+                    derivation.address
+                );
+                break;
+            }
+            "5" => {
+                let deployer = request_deployer_address();
+                let scheme = request_scheme();
+
+                print!("\x1b[36mEnter salt prefix ({}):\x1b[0m ", scheme.encoding);
+                io::stdout().flush().unwrap();
+                let mut salt_prefix = String::new();
+                io::stdin().read_line(&mut salt_prefix).unwrap();
+                let salt_prefix = salt_prefix.trim();
+
+                print!("\x1b[36mCount leading or total zero bytes (leading/total, empty for leading):\x1b[0m ");
+                let score: ZeroScore = loop {
+                    io::stdout().flush().unwrap();
+                    let mut score = String::new();
+                    io::stdin().read_line(&mut score).unwrap();
+                    let score = score.trim();
+
+                    if score.is_empty() {
+                        break ZeroScore::default();
+                    }
+                    match score.parse() {
+                        Ok(score) => break score,
+                        Err(e) => print!(
+                            "\x1b[36mInvalid score ({}). Please enter leading or total:\x1b[0m ",
+                            e
+                        ),
+                    }
+                };
+
+                let target = request_number("Enter target number of zero bytes (1-20):");
+                let seconds = request_number("Enter maximum search time in seconds:");
+                let limits = SearchLimits::new()
+                    .with_timeout(Duration::from_secs(seconds as u64))
+                    .with_cancellation(stop_on_ctrl_c().into())
+                    .with_progress(status_line());
+
+                println!("\x1b[36mSearching...\x1b[0m");
+                let best = generate_salt_zeros(
+                    &deployer,
+                    salt_prefix,
+                    score,
+                    target,
+                    &limits,
+                    AUTO_THREADS,
+                    scheme,
+                );
+                clear_status_line();
+                let best = match best {
+                    Ok(best) => best,
+                    Err(e) => {
+                        println!("\x1b[31m{}\x1b[0m", e);
+                        continue 'menu;
+                    }
+                };
+
+                if best.score < target {
+                    println!(
+                        "\x1b[33mSearch stopped before the target, showing the best address found.\x1b[0m"
+                    );
+                }
+                println!("\x1b[32mAddress:\x1b[0m {}", best.mined.address);
+                println!("\x1b[32mZero bytes ({}):\x1b[0m {}", score, best.score);
+                println!(
+                    "\x1b[32mEstimated calldata gas saved per call:\x1b[0m {}",
+                    best.gas_saved_per_call
+                );
+                println!("\x1b[32mSalt string:\x1b[0m {}", best.mined.preimage);
+                println!("\x1b[32mEncoded salt:\x1b[0m {}", best.mined.salt);
+                break;
+            }
+            "6" => {
+                print!("\x1b[36mEnter checkpoint file:\x1b[0m ");
+                io::stdout().flush().unwrap();
+                let mut path = String::new();
+                io::stdin().read_line(&mut path).unwrap();
+                let path = PathBuf::from(path.trim());
+
+                let mut state = match SearchState::load(&path) {
+                    Ok(state) => {
+                        println!(
+                            "\x1b[36mResuming search for {} at counter {}.\x1b[0m",
+                            state.pattern, state.next
+                        );
+                        state
+                    }
+                    Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                        let deployer = request_deployer_address();
+                        let scheme = request_scheme();
+
+                        print!("\x1b[36mEnter salt prefix ({}):\x1b[0m ", scheme.encoding);
+                        io::stdout().flush().unwrap();
+                        let mut salt_prefix = String::new();
+                        io::stdin().read_line(&mut salt_prefix).unwrap();
+
+                        print!("\x1b[36mEnter address pattern (hex prefix, '?' for any nibble, '*' for any run, e.g. dead, *beef, 00??*cafe):\x1b[0m ");
+                        io::stdout().flush().unwrap();
+                        let mut pattern = String::new();
+                        io::stdin().read_line(&mut pattern).unwrap();
+
+                        let checksum = request_confirmation(
+                            "Match the checksummed letter case of the pattern?",
+                        );
+
+                        print!("\x1b[36mEnter seed (empty for random):\x1b[0m ");
+                        io::stdout().flush().unwrap();
+                        let mut seed = String::new();
+                        io::stdin().read_line(&mut seed).unwrap();
+
+                        SearchState::new(
+                            &deployer,
+                            salt_prefix.trim(),
+                            pattern.trim(),
+                            checksum,
+                            scheme,
+                            seed.trim().parse().ok(),
+                        )
+                    }
+                    Err(e) => {
+                        println!("\x1b[31mCannot read checkpoint file: {}\x1b[0m", e);
+                        continue 'menu;
+                    }
+                };
+
+                if !confirm_difficulty(
+                    &state.deployer,
+                    &state.salt_prefix,
+                    &state.pattern,
+                    state.checksum,
+                    AUTO_THREADS,
+                    state.scheme,
+                ) {
+                    continue 'menu;
+                }
+                let stop = stop_on_ctrl_c();
+
+                println!(
+                    "\x1b[36mSearching with seed {}, press Ctrl-C to stop and save progress...\x1b[0m",
+                    state.seed
+                );
+                let result = generate_salt_resumable(
+                    &mut state,
+                    AUTO_THREADS,
+                    &stop,
+                    Some(&path),
+                    Some(&status_line()),
+                );
+                clear_status_line();
+
+                match result {
+                    Ok(Some(mined)) => {
+                        println!("\x1b[32mVanity address:\x1b[0m {}", mined.address);
+                        println!("\x1b[32mSalt string:\x1b[0m {}", mined.preimage);
+                        println!("\x1b[32mEncoded salt:\x1b[0m {}", mined.salt);
+                    }
+                    Ok(None) => println!(
+                        "\x1b[33mStopped at counter {}, progress saved to {}.\x1b[0m",
+                        state.next,
+                        path.display()
+                    ),
+                    Err(e) => println!("\x1b[31m{}\x1b[0m", e),
+                }
+                break;
+            }
+            "7" => {
+                let deployer = request_deployer_address();
+                let mut scheme = SaltScheme::from(request_mode());
+                scheme.sender = request_sender_binding();
+
+                print!("\x1b[36mEnter fixed leading salt bytes (hex, at most {} bytes, empty for none):\x1b[0m ", MAX_FIXED_BYTES);
+                let fixed = loop {
+                    io::stdout().flush().unwrap();
+                    let mut fixed = String::new();
+                    io::stdin().read_line(&mut fixed).unwrap();
+                    let fixed = fixed.trim();
+
+                    match hex::decode(fixed.strip_prefix("0x").unwrap_or(fixed)) {
+                        Ok(fixed) if fixed.len() <= MAX_FIXED_BYTES => break fixed,
+                        _ => {
+                            print!("\x1b[36mInvalid fixed bytes. Please enter fixed bytes:\x1b[0m ")
+                        }
+                    }
+                };
+
+                let limits = SearchLimits::new()
+                    .with_cancellation(stop_on_ctrl_c().into())
+                    .with_progress(status_line());
+
+                print!("\x1b[36mEnter address pattern (hex prefix, '?' for any nibble, '*' for any run, e.g. dead, *beef, 00??*cafe):\x1b[0m ");
+                let salt = loop {
+                    io::stdout().flush().unwrap();
+                    let mut pattern = String::new();
+                    io::stdin().read_line(&mut pattern).unwrap();
+
+                    // String salts cost one keccak more than raw salts, so this overestimates the time a little.
+                    if !confirm_difficulty(
+                        &deployer,
+                        "",
+                        pattern.trim(),
+                        false,
+                        AUTO_THREADS,
+                        scheme,
+                    ) {
+                        print!("\x1b[36mPlease enter pattern:\x1b[0m ");
+                        continue;
+                    }
+
+                    let result = generate_raw_salt_multithread(
+                        &deployer,
+                        &fixed,
+                        pattern.trim(),
+                        AUTO_THREADS,
+                        scheme,
+                        &limits,
+                    );
+                    clear_status_line();
+
+                    match result {
+                        Ok(salt) => break salt,
+                        Err(
+                            e @ (Create3GenerateSaltError::InvalidPatternChar { .. }
+                            | Create3GenerateSaltError::PatternTooLong
+                            | Create3GenerateSaltError::TooManyFixedBytes),
+                        ) => print!(
+                            "\x1b[36mInvalid pattern ({}). Please enter pattern:\x1b[0m ",
+                            e
+                        ),
+                        Err(e) => {
+                            println!("\x1b[31m{}\x1b[0m", e);
+                            continue 'menu;
+                        }
+                    }
+                };
+
+                println!("\x1b[32mVanity address:\x1b[0m {}", salt.address);
+                println!("\x1b[32mRaw salt:\x1b[0m {}", salt.salt);
                 break;
             }
             _ => {
@@ -133,22 +485,256 @@ fn main() {
     }
 }
 
-fn request_deployer_address() -> Vec<u8> {
+fn request_deployer_address() -> Address {
     print!("\x1b[36mEnter deployer address:\x1b[0m ");
     loop {
         io::stdout().flush().unwrap();
         let mut deployer = String::new();
         io::stdin().read_line(&mut deployer).unwrap();
-        let deployer = deployer.trim().trim_start_matches("0x");
-
-        if !deployer.chars().all(|c| c.is_ascii_hexdigit()) {
-            print!("\x1b[36mInput was not hex encoded. Please enter deployer:\x1b[0m ");
-            continue;
-        } else if deployer.len() != 40 {
-            print!("\x1b[36mInput has an incorrect length (expected 40 without '0x' prefix). Please enter deployer:\x1b[0m ");
-            continue;
+
+        match Address::from_str_checksummed(&deployer) {
+            Ok(deployer) => break deployer,
+            Err(Create3ParseError::NotHexEncoded) => {
+                print!("\x1b[36mInput was not hex encoded. Please enter deployer:\x1b[0m ")
+            }
+            Err(Create3ParseError::InvalidLength) => {
+                print!("\x1b[36mInput has an incorrect length (expected 40 without '0x' prefix). Please enter deployer:\x1b[0m ")
+            }
+            Err(Create3ParseError::InvalidChecksum) => {
+                print!("\x1b[36mInput does not match its EIP-55 checksum. Please enter deployer:\x1b[0m ")
+            }
+        }
+    }
+}
+
+fn request_mode() -> DeployMode {
+    print!("\x1b[36mEnter CREATE3 factory (solady/solmate/zeframlou/sequence/createx/axelar:<hash>/custom:<hash>/create2:<init code hash>, empty for solady):\x1b[0m ");
+    loop {
+        io::stdout().flush().unwrap();
+        let mut mode = String::new();
+        io::stdin().read_line(&mut mode).unwrap();
+        let mode = mode.trim();
+
+        if mode.is_empty() {
+            break DeployMode::default();
+        }
+
+        match mode.parse() {
+            Ok(mode) => break mode,
+            Err(e) => print!(
+                "\x1b[36mInvalid factory ({}). Please enter factory:\x1b[0m ",
+                e
+            ),
+        }
+    }
+}
+
+fn request_scheme() -> SaltScheme {
+    let scheme = SaltScheme::new(request_mode(), request_encoding());
+    match request_sender_binding() {
+        Some(sender) => scheme.with_sender(sender),
+        None => scheme,
+    }
+}
+
+fn request_sender_binding() -> Option<SenderBinding> {
+    print!("\x1b[36mEnter msg.sender if the factory hashes it into the salt (packed:<address>/abi-encode:<address>, empty for none):\x1b[0m ");
+    loop {
+        io::stdout().flush().unwrap();
+        let mut sender = String::new();
+        io::stdin().read_line(&mut sender).unwrap();
+        let sender = sender.trim();
+
+        if sender.is_empty() {
+            break None;
         }
 
-        break hex::decode(deployer).unwrap();
+        match sender.parse() {
+            Ok(sender) => break Some(sender),
+            Err(e) => print!(
+                "\x1b[36mInvalid sender binding ({}). Please enter sender binding:\x1b[0m ",
+                e
+            ),
+        }
     }
 }
+
+fn request_encoding() -> SaltEncoding {
+    print!("\x1b[36mEnter salt encoding (keccak256/abi-encode/bytes32/uint256/raw, empty for keccak256):\x1b[0m ");
+    loop {
+        io::stdout().flush().unwrap();
+        let mut encoding = String::new();
+        io::stdin().read_line(&mut encoding).unwrap();
+        let encoding = encoding.trim();
+
+        if encoding.is_empty() {
+            break SaltEncoding::default();
+        }
+
+        match encoding.parse() {
+            Ok(encoding) => break encoding,
+            Err(e) => print!(
+                "\x1b[36mInvalid salt encoding ({}). Please enter salt encoding:\x1b[0m ",
+                e
+            ),
+        }
+    }
+}
+
+/// Returns a flag that is set by the first Ctrl-C, so a running search can stop. A second Ctrl-C exits immediately.
+fn stop_on_ctrl_c() -> Arc<AtomicBool> {
+    let stop = Arc::new(AtomicBool::new(false));
+    signal_hook::flag::register_conditional_shutdown(SIGINT, 1, stop.clone()).unwrap();
+    signal_hook::flag::register(SIGINT, stop.clone()).unwrap();
+    stop
+}
+
+/// Runs the searches on one thread per available core.
+const AUTO_THREADS: usize = 0;
+
+/// The expected search time above which a search is considered infeasible and has to be confirmed.
+const INFEASIBLE_SEARCH: Duration = Duration::from_secs(365 * 86400);
+
+/// Prints how long a search for the pattern is expected to take at the measured hashrate, and asks for confirmation
+/// if it is infeasible. Invalid patterns and salt prefixes are let through, so the search reports the error.
+fn confirm_difficulty(
+    deployer: &Address,
+    salt_prefix: &str,
+    pattern: &str,
+    checksum: bool,
+    thread_count: usize,
+    scheme: SaltScheme,
+) -> bool {
+    let pattern = if checksum {
+        Pattern::new_checksum(pattern)
+    } else {
+        Pattern::new(pattern)
+    };
+    let Ok(pattern) = pattern else {
+        return true;
+    };
+    let measured = measure_hashrate(
+        deployer,
+        salt_prefix,
+        thread_count,
+        scheme,
+        Duration::from_millis(500),
+    );
+    let Ok(hashrate) = measured else {
+        return true;
+    };
+
+    let difficulty = Difficulty::new(&pattern);
+    let time = |attempts: f64| match Difficulty::time_for(attempts, hashrate) {
+        Some(time) => format_duration(time),
+        None => "forever".to_owned(),
+    };
+    println!(
+        "\x1b[36mExpected {} attempts at {}/s: {} on average, {} / {} / {} for a 50% / 90% / 99% chance.\x1b[0m",
+        format_count(difficulty.expected_attempts),
+        format_count(hashrate),
+        time(difficulty.expected_attempts),
+        time(difficulty.attempts_50),
+        time(difficulty.attempts_90),
+        time(difficulty.attempts_99)
+    );
+
+    match difficulty.expected_time(hashrate) {
+        Some(expected) if expected < INFEASIBLE_SEARCH => true,
+        _ => {
+            println!("\x1b[31mThis search is not expected to finish within a year.\x1b[0m");
+            request_confirmation("Start it anyway?")
+        }
+    }
+}
+
+/// Returns a progress hook that keeps a live status line of a running search on stderr.
+fn status_line() -> ProgressHook {
+    ProgressHook::new(Duration::from_millis(250), |progress: &Progress| {
+        let threads = progress.thread_hashrates.len().max(1);
+        let eta = match progress.eta() {
+            Some(eta) => format_duration(eta),
+            None => "unknown".to_owned(),
+        };
+        eprint!(
+            "\r\x1b[2K\x1b[90m{} attempts in {} | {}/s ({}/s per thread x {}) | expected in {}\x1b[0m",
+            format_count(progress.attempts as f64),
+            format_duration(progress.elapsed),
+            format_count(progress.hashrate),
+            format_count(progress.hashrate / threads as f64),
+            threads,
+            eta
+        );
+        io::stderr().flush().unwrap();
+    })
+}
+
+/// Erases the status line left by `status_line`.
+fn clear_status_line() {
+    eprint!("\r\x1b[2K");
+    io::stderr().flush().unwrap();
+}
+
+/// Formats a count with a k/M/G/T suffix, e.g. `1.50M`, or in scientific notation past `1000T`.
+fn format_count(count: f64) -> String {
+    if count >= 1e15 {
+        return format!("{:.2e}", count);
+    }
+    let units = ["", "k", "M", "G", "T"];
+    let mut count = count;
+    let mut unit = 0;
+    while count >= 1000.0 && unit < units.len() - 1 {
+        count /= 1000.0;
+        unit += 1;
+    }
+    if unit == 0 {
+        format!("{:.0}", count)
+    } else {
+        format!("{:.2}{}", count, units[unit])
+    }
+}
+
+/// Formats a duration with its two largest units, e.g. `3h 25m`.
+fn format_duration(duration: Duration) -> String {
+    let secs = duration.as_secs();
+    let units = [
+        (365 * 86400, "y"),
+        (86400, "d"),
+        (3600, "h"),
+        (60, "m"),
+        (1, "s"),
+    ];
+    match units.iter().position(|(unit, _)| secs >= *unit) {
+        Some(i) if i + 1 < units.len() => format!(
+            "{}{} {}{}",
+            secs / units[i].0,
+            units[i].1,
+            secs % units[i].0 / units[i + 1].0,
+            units[i + 1].1
+        ),
+        Some(_) => format!("{}s", secs),
+        None => "<1s".to_owned(),
+    }
+}
+
+fn request_number(question: &str) -> u32 {
+    print!("\x1b[36m{}\x1b[0m ", question);
+    loop {
+        io::stdout().flush().unwrap();
+        let mut number = String::new();
+        io::stdin().read_line(&mut number).unwrap();
+
+        match number.trim().parse() {
+            Ok(number) => break number,
+            Err(_) => print!("\x1b[36mInput was not a number. {}\x1b[0m ", question),
+        }
+    }
+}
+
+fn request_confirmation(question: &str) -> bool {
+    print!("\x1b[36m{} (y/N):\x1b[0m ", question);
+    io::stdout().flush().unwrap();
+    let mut answer = String::new();
+    io::stdin().read_line(&mut answer).unwrap();
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
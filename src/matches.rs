@@ -0,0 +1,180 @@
+use std::collections::HashSet;
+
+use crate::{
+    errors::Create3GenerateSaltError,
+    search::{generate_salt_resumable, SearchState},
+    Address, CancellationToken, MinedSalt, Pattern, SaltScheme,
+};
+
+/// Keeps generating salts for a given deployer, salt prefix and address pattern, one per call to `next`.
+///
+/// The search only runs while `next` is called, which blocks until the next salt is found. Every match resumes the
+/// search after it, so no salt is tested twice, and a salt giving an address that was already returned is skipped.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt_prefix` - A string representing the prefix to append to the generated salts.
+/// * `pattern` - The pattern the resulting addresses should match, case-insensitive or checksummed (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salts are mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+///
+/// # Returns
+///
+/// An iterator of the generated salts, with the salts tested since the previous match as their `attempts`, which ends
+/// after an error or once cancelled.
+pub fn salt_matches(
+    deployer: &Address,
+    salt_prefix: &str,
+    pattern: &Pattern,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+) -> SaltMatches {
+    SaltMatches {
+        state: SearchState::new(
+            deployer,
+            salt_prefix,
+            &pattern.to_string(),
+            pattern.is_checksum(),
+            scheme,
+            None,
+        ),
+        thread_count,
+        cancellation: CancellationToken::new(),
+        seen: HashSet::new(),
+        done: false,
+    }
+}
+
+/// The salts found by a search started with `salt_matches`.
+#[derive(Debug, Clone)]
+pub struct SaltMatches {
+    state: SearchState,
+    thread_count: usize,
+    cancellation: CancellationToken,
+    /// The addresses returned so far.
+    seen: HashSet<Address>,
+    done: bool,
+}
+
+impl SaltMatches {
+    /// Returns the iterator stopped by `cancellation`, which also interrupts a `next` call waiting for a salt.
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        SaltMatches {
+            cancellation,
+            ..self
+        }
+    }
+}
+
+impl Iterator for SaltMatches {
+    type Item = Result<MinedSalt, Create3GenerateSaltError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.done {
+            let found = generate_salt_resumable(
+                &mut self.state,
+                self.thread_count,
+                &self.cancellation.0,
+                None,
+                None,
+            );
+            let mined = match found {
+                Ok(Some(mined)) => mined,
+                Ok(None) => break,
+                Err(e) => {
+                    self.done = true;
+                    return Some(Err(e));
+                }
+            };
+            if self.seen.insert(mined.address) {
+                return Some(Ok(mined));
+            }
+        }
+        self.done = true;
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{thread, time::Duration};
+
+    use crate::{
+        matches::salt_matches, Address, CancellationToken, Create3GenerateSaltError,
+        Create3Variant, Pattern, SaltEncoding, SaltScheme,
+    };
+
+    fn deployer() -> Address {
+        "5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap()
+    }
+
+    #[test]
+    fn should_yield_distinct_matches() {
+        let pattern = Pattern::new("a").unwrap();
+
+        for thread_count in [1, 3] {
+            let matches: Vec<_> = salt_matches(
+                &deployer(),
+                "pfx_",
+                &pattern,
+                thread_count,
+                Create3Variant::Solady,
+            )
+            .take(20)
+            .map(Result::unwrap)
+            .collect();
+
+            assert_eq!(matches.len(), 20);
+            for (i, mined) in matches.iter().enumerate() {
+                assert!(mined.preimage.starts_with("pfx_"));
+                assert!(pattern.matches(&mined.address));
+                assert_eq!(
+                    SaltScheme::from(Create3Variant::Solady)
+                        .calc_addr_with_bytes(&deployer(), &mined.salt),
+                    mined.address
+                );
+                assert!(matches[..i]
+                    .iter()
+                    .all(|other| other.address != mined.address));
+            }
+        }
+    }
+
+    #[test]
+    fn should_stop_when_cancelled() {
+        let cancellation = CancellationToken::new();
+        let impossible = Pattern::new(&"0".repeat(40)).unwrap();
+        let mut matches = salt_matches(&deployer(), "", &impossible, 2, Create3Variant::Solady)
+            .with_cancellation(cancellation.clone());
+
+        thread::scope(|scope| {
+            scope.spawn(|| {
+                thread::sleep(Duration::from_millis(50));
+                cancellation.cancel();
+            });
+            assert!(matches.next().is_none());
+        });
+        assert!(matches.next().is_none());
+    }
+
+    #[test]
+    fn should_end_after_an_error() {
+        // A raw salt prefix has to leave room for the 16 hex digits of the counter.
+        let raw = SaltScheme::new(Create3Variant::Solady, SaltEncoding::Raw);
+        let mut matches = salt_matches(
+            &deployer(),
+            &"0".repeat(60),
+            &Pattern::new("a").unwrap(),
+            1,
+            raw,
+        );
+        assert_eq!(
+            matches.next(),
+            Some(Err(Create3GenerateSaltError::InvalidSaltPrefix))
+        );
+        assert_eq!(matches.next(), None);
+    }
+}
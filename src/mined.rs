@@ -0,0 +1,72 @@
+use std::time::Duration;
+
+use crate::{Address, Salt, SaltScheme};
+
+/// A salt found by a search, with everything needed to deploy with it and to tell how it was found.
+///
+/// With the `serde` feature it can be serialized, e.g. to JSON or TOML, with addresses and salts as hex strings.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MinedSalt {
+    /// The salt string, which `scheme.encoding` turns into `salt`.
+    pub preimage: String,
+    /// The bytes32 salt passed to the factory.
+    pub salt: Salt,
+    /// The address the contract is deployed at.
+    pub address: Address,
+    /// The create3 deployer address.
+    pub deployer: Address,
+    /// The CREATE3 factory variant or CREATE2 init code, salt encoding and sender binding the salt was mined for.
+    pub scheme: SaltScheme,
+    /// The normalized pattern the address matches (see `Pattern`).
+    pub pattern: String,
+    /// Whether the letter case of the pattern has to match the EIP-55 checksummed address.
+    pub checksum: bool,
+    /// The number of salts tested by all threads together when the search stopped. A multi-pattern search counts up
+    /// to the moment this salt was found, and a resumable search counts from where it resumed up to this salt.
+    pub attempts: u64,
+    /// The time the search took to find the salt.
+    pub elapsed: Duration,
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use std::time::Duration;
+
+    use crate::{Create3Variant, MinedSalt, SaltEncoding, SaltScheme, SenderBinding};
+
+    #[test]
+    fn should_round_trip_through_json() {
+        let deployer = "0x5e17b14ADd6c386305A32928F985b29bbA34Eff5"
+            .parse()
+            .unwrap();
+        let scheme = SaltScheme::new(Create3Variant::Solady, SaltEncoding::RightPadded)
+            .with_sender(SenderBinding::Packed(deployer));
+        let salt = scheme.encoding.encode("pfx_42").unwrap();
+        let mined = MinedSalt {
+            preimage: "pfx_42".to_string(),
+            salt,
+            address: scheme.calc_addr_with_bytes(&deployer, &salt),
+            deployer,
+            scheme,
+            pattern: "ab*".to_string(),
+            checksum: false,
+            attempts: 42,
+            elapsed: Duration::from_millis(1500),
+        };
+
+        let json = serde_json::to_value(&mined).unwrap();
+        assert_eq!(
+            json["deployer"],
+            "0x5e17b14ADd6c386305A32928F985b29bbA34Eff5"
+        );
+        assert_eq!(json["salt"], salt.to_string());
+        assert_eq!(json["scheme"]["mode"], "solady");
+        assert_eq!(json["scheme"]["encoding"], "bytes32");
+        assert_eq!(
+            json["scheme"]["sender"],
+            "packed:0x5e17b14ADd6c386305A32928F985b29bbA34Eff5"
+        );
+        assert_eq!(serde_json::from_value::<MinedSalt>(json).unwrap(), mined);
+    }
+}
@@ -0,0 +1,398 @@
+use std::{
+    sync::{
+        atomic::{AtomicBool, AtomicU64, AtomicUsize, Ordering},
+        mpsc,
+    },
+    time::Instant,
+};
+
+use crate::{
+    errors::Create3GenerateSaltError,
+    hasher::{AddressHasher, BATCH},
+    pool::Pool,
+    search::{encode_candidates, report, watch, write_candidate},
+    Address, MinedSalt, Pattern, SaltScheme, SearchLimits,
+};
+
+/// The child index of a `?` in a trie node, after the 16 nibbles.
+const WILDCARD: usize = 16;
+
+/// A node of the prefix trie, indexed by nibble and `?`.
+#[derive(Debug, Clone, Default)]
+struct TrieNode {
+    children: [Option<u32>; 17],
+    /// The patterns ending at this node.
+    patterns: Vec<usize>,
+}
+
+/// A set of address patterns that are matched at once.
+///
+/// Case-insensitive prefixes are stored in a nibble trie, so an address is compared against all of them in one walk.
+/// Every other pattern is matched on its own.
+#[derive(Debug, Clone)]
+pub(crate) struct PatternSet {
+    nodes: Vec<TrieNode>,
+    others: Vec<(usize, Pattern)>,
+}
+
+impl PatternSet {
+    pub(crate) fn new(patterns: &[Pattern]) -> Self {
+        let mut set = PatternSet {
+            nodes: vec![TrieNode::default()],
+            others: Vec::new(),
+        };
+
+        for (index, pattern) in patterns.iter().enumerate() {
+            let Some(prefix) = pattern.as_prefix() else {
+                set.others.push((index, pattern.clone()));
+                continue;
+            };
+
+            let mut node = 0;
+            for c in prefix {
+                let child = match c {
+                    b'?' => WILDCARD,
+                    c => (*c as char).to_digit(16).unwrap() as usize,
+                };
+                node = match set.nodes[node].children[child] {
+                    Some(next) => next as usize,
+                    None => {
+                        set.nodes.push(TrieNode::default());
+                        let next = set.nodes.len() - 1;
+                        set.nodes[node].children[child] = Some(next as u32);
+                        next
+                    }
+                };
+            }
+            set.nodes[node].patterns.push(index);
+        }
+
+        set
+    }
+
+    /// Appends the index of every pattern the address matches to `matches`.
+    pub(crate) fn matches(&self, address: &Address, matches: &mut Vec<usize>) {
+        let nibble = |i: usize| (address.0[i / 2] >> (4 * (1 - i % 2))) as usize & 0x0f;
+
+        // Depth-first walk following both the nibble and the `?` child at every depth. Every step pops one node and
+        // pushes at most two, so the stack never holds more than one node per depth plus one.
+        let mut stack = [(0u32, 0usize); Address::LEN * 2 + 2];
+        let mut len = 1;
+        while len > 0 {
+            len -= 1;
+            let (node, depth) = stack[len];
+            let node = &self.nodes[node as usize];
+            matches.extend_from_slice(&node.patterns);
+            if depth == Address::LEN * 2 {
+                continue;
+            }
+            for child in [nibble(depth), WILDCARD] {
+                if let Some(next) = node.children[child] {
+                    stack[len] = (next, depth + 1);
+                    len += 1;
+                }
+            }
+        }
+
+        for (index, pattern) in self.others.iter() {
+            if pattern.matches(address) {
+                matches.push(*index);
+            }
+        }
+    }
+}
+
+/// Generates a salt for each of several address patterns at once, hashing every salt only once for all patterns.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt_prefix` - A string representing the prefix to append to the generated salts.
+/// * `patterns` - The patterns the resulting addresses should match, case-insensitive or checksummed (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salts are mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error, and how it reports its progress. The
+///   progress expects as many attempts as the hardest pattern. `SearchLimits::default()` never gives up.
+/// * `on_found` - Called on the calling thread with the index of a pattern and its salt as soon as it is found, also
+///   when the search later gives up.
+///
+/// # Returns
+///
+/// The generated salt for each pattern, in the order of `patterns`. Their `attempts` count the salts tested by all
+/// threads for all patterns together, up to the moment each salt was found.
+pub fn generate_salts_multithread(
+    deployer: &Address,
+    salt_prefix: &str,
+    patterns: &[Pattern],
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+    mut on_found: impl FnMut(usize, &MinedSalt),
+) -> Result<Vec<MinedSalt>, Create3GenerateSaltError> {
+    let scheme = scheme.into();
+    scheme.encoding.validate_salt_prefix(salt_prefix)?;
+    let seed: u64 = rand::random();
+
+    let pool = Pool::new(thread_count);
+    let thread_count = pool.thread_count() as u64;
+    if patterns.is_empty() {
+        pool.stop();
+    }
+    let set = PatternSet::new(patterns);
+    let found: Vec<AtomicBool> = patterns.iter().map(|_| AtomicBool::new(false)).collect();
+    let remaining = AtomicUsize::new(patterns.len());
+    let mut results: Vec<Option<MinedSalt>> = vec![None; patterns.len()];
+    let (sender, receiver) = mpsc::channel::<(usize, MinedSalt)>();
+    let attempts: Vec<AtomicU64> = (0..thread_count).map(|_| AtomicU64::new(0)).collect();
+    let end = limits.end(0);
+    let started = Instant::now();
+
+    let (set, found, remaining, attempts, pool_ref) = (&set, &found, &remaining, &attempts, &pool);
+    pool.run(
+        move |thread| {
+            let pool = pool_ref;
+            let hasher = AddressHasher::new(deployer, &scheme);
+            let mut salts: [String; BATCH] = Default::default();
+            let mut matches = Vec::new();
+
+            // Every thread walks its own disjoint counters, a batch at a time. The workers stop themselves, as the
+            // caller may be busy running one of them on a small rayon pool.
+            let mut counter = thread as u64;
+            while !pool.is_stopped()
+                && counter < end
+                && !limits.is_cancelled()
+                && !limits.is_expired()
+            {
+                let counters: [u64; BATCH] =
+                    std::array::from_fn(|i| counter + i as u64 * thread_count);
+                counter += BATCH as u64 * thread_count;
+                for (salt, counter) in salts.iter_mut().zip(counters) {
+                    write_candidate(scheme.encoding, salt_prefix, seed, counter, salt);
+                }
+                let salt_bytes = encode_candidates(&hasher, scheme.encoding, &salts);
+                let vanity_addrs = hasher.calc_addrs(&salt_bytes);
+                let tested = counters.iter().filter(|counter| **counter < end).count();
+                attempts[thread].fetch_add(tested as u64, Ordering::Relaxed);
+
+                for i in (0..BATCH).filter(|i| counters[*i] < end) {
+                    matches.clear();
+                    set.matches(&vanity_addrs[i], &mut matches);
+                    for index in matches.iter() {
+                        // Skip patterns that already have a salt, so easy patterns do not flood the channel.
+                        if !found[*index].swap(true, Ordering::Relaxed) {
+                            let mined = MinedSalt {
+                                preimage: salts[i].clone(),
+                                salt: salt_bytes[i],
+                                address: vanity_addrs[i],
+                                deployer: *deployer,
+                                scheme,
+                                pattern: patterns[*index].to_string(),
+                                checksum: patterns[*index].is_checksum(),
+                                attempts: attempts
+                                    .iter()
+                                    .map(|attempts| attempts.load(Ordering::Relaxed))
+                                    .sum(),
+                                elapsed: started.elapsed(),
+                            };
+                            sender.send((*index, mined)).unwrap();
+                            pool.wake();
+                            if remaining.fetch_sub(1, Ordering::Relaxed) == 1 {
+                                pool.stop();
+                            }
+                        }
+                    }
+                }
+            }
+        },
+        || {
+            let expected_attempts = patterns
+                .iter()
+                .map(Pattern::expected_attempts)
+                .fold(0.0, f64::max);
+            let report = || report(attempts, started, expected_attempts);
+            watch(&pool, limits, report, || {
+                for (index, mined) in receiver.try_iter() {
+                    on_found(index, &mined);
+                    results[index] = Some(mined);
+                }
+                Ok(())
+            })
+        },
+    )?;
+    pool.result()?;
+
+    // Every salt sent before the workers finished is received before giving up.
+    for (index, mined) in receiver.try_iter() {
+        on_found(index, &mined);
+        results[index] = Some(mined);
+    }
+    // Without a salt for every pattern, the threads stopped because of the limits.
+    results
+        .into_iter()
+        .map(|mined| mined.ok_or_else(|| limits.error()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use rand::Rng;
+
+    use crate::{
+        calc_addr_with_bytes,
+        errors::Create3GenerateSaltError,
+        multi::{generate_salts_multithread, PatternSet},
+        Address, CancellationToken, Create3Variant, Pattern, SaltEncoding, SaltScheme,
+        SearchLimits,
+    };
+
+    #[test]
+    fn pattern_set_should_match_like_each_pattern() {
+        let patterns: Vec<Pattern> = ["0", "00", "a", "a?", "?b", "ab12", "*c", "*d*", "0x1", ""]
+            .iter()
+            .map(|pattern| pattern.parse().unwrap())
+            .collect();
+        let set = PatternSet::new(&patterns);
+
+        let mut rng = rand::thread_rng();
+        let mut matches = Vec::new();
+        for i in 0..2000 {
+            let mut address = Address(rng.gen());
+            // Make sure the longer prefixes are hit.
+            if i % 4 == 0 {
+                address.0[..2].copy_from_slice(&[0xab, 0x12]);
+            }
+
+            matches.clear();
+            set.matches(&address, &mut matches);
+            matches.sort();
+            let expected: Vec<usize> = (0..patterns.len())
+                .filter(|i| patterns[*i].matches(&address))
+                .collect();
+            assert_eq!(matches, expected);
+        }
+    }
+
+    #[test]
+    fn should_generate_a_salt_for_every_pattern() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let patterns = [
+            Pattern::new("0").unwrap(),
+            Pattern::new("abc").unwrap(),
+            Pattern::new("*12").unwrap(),
+            Pattern::new("?f*").unwrap(),
+            Pattern::new("*c0*").unwrap(),
+            Pattern::new("0").unwrap(),
+            Pattern::new_checksum("Ab").unwrap(),
+            Pattern::new_checksum("*e").unwrap(),
+        ];
+        let mut reported = Vec::new();
+
+        let results = generate_salts_multithread(
+            deployer,
+            "testpfx_",
+            &patterns,
+            6,
+            Create3Variant::Solady,
+            &SearchLimits::default(),
+            |index, salt| reported.push((index, salt.clone())),
+        )
+        .unwrap();
+
+        assert_eq!(results.len(), patterns.len());
+        assert_eq!(reported.len(), patterns.len());
+        for (index, salt) in reported.iter() {
+            assert_eq!(results[*index], *salt);
+        }
+        for (pattern, mined) in patterns.iter().zip(results.iter()) {
+            assert!(mined.preimage.starts_with("testpfx_"));
+            let addr = calc_addr_with_bytes(deployer, &mined.salt, Create3Variant::Solady);
+            assert_eq!(mined.address, addr);
+            assert_eq!(mined.pattern, pattern.to_string());
+            assert_eq!(mined.checksum, pattern.is_checksum());
+            assert!(pattern.matches(&addr));
+        }
+    }
+
+    #[test]
+    fn should_handle_no_patterns() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let results = generate_salts_multithread(
+            deployer,
+            "",
+            &[],
+            2,
+            Create3Variant::Solady,
+            &SearchLimits::default(),
+            |_, _| {},
+        )
+        .unwrap();
+        assert!(results.is_empty());
+    }
+
+    #[test]
+    fn should_give_up_on_an_impossible_pattern() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        // 40 nibbles will not be found by any of these searches, unlike the single nibble.
+        let patterns = [
+            Pattern::new("0").unwrap(),
+            Pattern::new(&"0".repeat(40)).unwrap(),
+        ];
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        let runs = [
+            (
+                SearchLimits::new().with_max_attempts(1000),
+                Create3GenerateSaltError::Timeout,
+            ),
+            (
+                SearchLimits::new().with_timeout(Duration::from_millis(50)),
+                Create3GenerateSaltError::Timeout,
+            ),
+            (
+                SearchLimits::new().with_cancellation(cancelled),
+                Create3GenerateSaltError::Cancelled,
+            ),
+        ];
+
+        for (limits, err) in runs {
+            let start = Instant::now();
+            let mut reported = Vec::new();
+            assert_eq!(
+                generate_salts_multithread(
+                    deployer,
+                    "",
+                    &patterns,
+                    3,
+                    Create3Variant::Solady,
+                    &limits,
+                    |index, _| reported.push(index)
+                ),
+                Err(err)
+            );
+            assert!(!reported.contains(&1));
+            assert!(start.elapsed() < Duration::from_secs(10));
+        }
+    }
+
+    #[test]
+    fn should_error_if_salt_prefix_cannot_be_encoded() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let scheme = SaltScheme::new(Create3Variant::Solady, SaltEncoding::Uint256);
+        assert_eq!(
+            generate_salts_multithread(
+                deployer,
+                "testpfx_",
+                &[Pattern::new("abc").unwrap()],
+                2,
+                scheme,
+                &SearchLimits::default(),
+                |_, _| {}
+            ),
+            Err(Create3GenerateSaltError::InvalidSaltPrefix)
+        );
+    }
+}
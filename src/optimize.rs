@@ -0,0 +1,400 @@
+use std::{
+    fmt::Display,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicU32, AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Instant,
+};
+
+use crate::{
+    errors::Create3GenerateSaltError,
+    hasher::{AddressHasher, BATCH},
+    pool::Pool,
+    search::{encode_candidates, report, watch, write_candidate},
+    Address, MinedSalt, SaltScheme, SearchLimits,
+};
+
+/// Calldata gas charged for a non-zero byte (EIP-2028).
+pub const CALLDATA_NONZERO_BYTE_GAS: u64 = 16;
+
+/// Calldata gas charged for a zero byte (EIP-2028).
+pub const CALLDATA_ZERO_BYTE_GAS: u64 = 4;
+
+/// How an address is scored when optimizing for gas.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ZeroScore {
+    /// The number of leading zero bytes, e.g. `0x0000...` scores 2.
+    #[default]
+    LeadingZeroBytes,
+    /// The number of zero bytes anywhere in the address, in the style of "efficient addresses".
+    TotalZeroBytes,
+}
+
+impl ZeroScore {
+    /// Scores an address. Higher is better, with a maximum of 20.
+    pub fn score(&self, address: &Address) -> u32 {
+        match self {
+            ZeroScore::LeadingZeroBytes => address.0.iter().take_while(|b| **b == 0).count() as u32,
+            ZeroScore::TotalZeroBytes => address.0.iter().filter(|b| **b == 0).count() as u32,
+        }
+    }
+
+    /// Returns the expected number of salts to test before one scores at least `target`.
+    ///
+    /// Every address byte is zero with a probability of 1 in 256, independently of the others.
+    pub fn expected_attempts(&self, target: u32) -> f64 {
+        let target = target.min(20) as i32;
+        match self {
+            ZeroScore::LeadingZeroBytes => 256f64.powi(target),
+            ZeroScore::TotalZeroBytes => {
+                // The number of zero bytes follows a binomial distribution over the 20 address bytes.
+                let p = 1.0 / 256f64;
+                let mut choose = 1.0;
+                let mut odds = 0.0;
+                for k in 0..=20 {
+                    if k >= target {
+                        odds += choose * p.powi(k) * (1.0 - p).powi(20 - k);
+                    }
+                    choose = choose * (20 - k) as f64 / (k + 1) as f64;
+                }
+                1.0 / odds
+            }
+        }
+    }
+}
+
+impl Display for ZeroScore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(match self {
+            ZeroScore::LeadingZeroBytes => "leading",
+            ZeroScore::TotalZeroBytes => "total",
+        })
+    }
+}
+
+/// Parses `leading` and `total`.
+impl FromStr for ZeroScore {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_lowercase().as_str() {
+            "leading" => Ok(ZeroScore::LeadingZeroBytes),
+            "total" => Ok(ZeroScore::TotalZeroBytes),
+            s => Err(format!("unknown score '{}'", s)),
+        }
+    }
+}
+
+/// Estimates the calldata gas saved every time the address is passed to a call, compared to an address without zero
+/// bytes.
+pub fn gas_saved_per_call(address: &Address) -> u64 {
+    let zero_bytes = ZeroScore::TotalZeroBytes.score(address) as u64;
+    zero_bytes * (CALLDATA_NONZERO_BYTE_GAS - CALLDATA_ZERO_BYTE_GAS)
+}
+
+/// The best salt found by an optimizing search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ScoredSalt {
+    /// The salt, with the resulting address and how it was found. Its pattern is `*`, as every address is scored.
+    pub mined: MinedSalt,
+    /// The score of the address.
+    pub score: u32,
+    /// The estimated calldata gas saved per call, see `gas_saved_per_call`.
+    pub gas_saved_per_call: u64,
+}
+
+/// Searches for the salt whose address scores highest, until the target score is reached or `limits` give up.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt_prefix` - A string representing the prefix to append to the generated salt.
+/// * `score` - How addresses are scored.
+/// * `target` - The score at which the search stops early.
+/// * `limits` - When the search stops and returns the best salt found so far, and how it reports its progress.
+///   `SearchLimits::default()` only stops at `target`.
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with a salt encoding and
+///   the `msg.sender` the factory binds into the salt.
+///
+/// # Returns
+///
+/// The best salt found, which reaches `target` unless `limits` gave up first. Fails with their `Timeout` or
+/// `Cancelled` error if they gave up before any salt was scored.
+pub fn generate_salt_zeros(
+    deployer: &Address,
+    salt_prefix: &str,
+    score: ZeroScore,
+    target: u32,
+    limits: &SearchLimits,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+) -> Result<ScoredSalt, Create3GenerateSaltError> {
+    let scheme = scheme.into();
+    scheme.encoding.validate_salt_prefix(salt_prefix)?;
+    let seed: u64 = rand::random();
+    let pool = Pool::new(thread_count);
+    let thread_count = pool.thread_count() as u64;
+
+    // The best score is read on every attempt, so it is kept outside the lock. It is stored plus one, so that 0 means
+    // no salt has been scored yet.
+    let best_score = AtomicU32::new(0);
+    let best: Mutex<Option<ScoredSalt>> = Mutex::new(None);
+    let attempts: Vec<AtomicU64> = (0..thread_count).map(|_| AtomicU64::new(0)).collect();
+    let end = limits.end(0);
+    let started = Instant::now();
+
+    pool.run(
+        |thread| {
+            let hasher = AddressHasher::new(deployer, &scheme);
+            let mut salts: [String; BATCH] = Default::default();
+
+            // Every thread walks its own disjoint counters, a batch at a time, and stops on its own once a limit is
+            // hit.
+            let mut counter = thread as u64;
+            while !pool.is_stopped()
+                && counter < end
+                && !limits.is_cancelled()
+                && !limits.is_expired()
+            {
+                let counters: [u64; BATCH] =
+                    std::array::from_fn(|i| counter + i as u64 * thread_count);
+                counter += BATCH as u64 * thread_count;
+                for (salt, counter) in salts.iter_mut().zip(counters) {
+                    write_candidate(scheme.encoding, salt_prefix, seed, counter, salt);
+                }
+                let salt_bytes = encode_candidates(&hasher, scheme.encoding, &salts);
+                let addresses = hasher.calc_addrs(&salt_bytes);
+                let tested = counters.iter().filter(|counter| **counter < end).count();
+                attempts[thread].fetch_add(tested as u64, Ordering::Relaxed);
+
+                for i in 0..BATCH {
+                    let address_score = score.score(&addresses[i]);
+                    if counters[i] >= end || address_score < best_score.load(Ordering::Relaxed) {
+                        continue;
+                    }
+
+                    let mut best = best.lock().unwrap();
+                    if best.as_ref().is_none_or(|best| address_score > best.score) {
+                        best_score.store(address_score + 1, Ordering::Relaxed);
+                        let mined = MinedSalt {
+                            preimage: salts[i].clone(),
+                            salt: salt_bytes[i],
+                            address: addresses[i],
+                            deployer: *deployer,
+                            scheme,
+                            pattern: "*".to_string(),
+                            checksum: false,
+                            // Counted once the search stops.
+                            attempts: 0,
+                            elapsed: started.elapsed(),
+                        };
+                        *best = Some(ScoredSalt {
+                            mined,
+                            score: address_score,
+                            gas_saved_per_call: gas_saved_per_call(&addresses[i]),
+                        });
+                    }
+                    if address_score >= target {
+                        pool.stop();
+                    }
+                }
+            }
+        },
+        || {
+            let report = || report(&attempts, started, score.expected_attempts(target));
+            watch(&pool, limits, report, || Ok(()))
+        },
+    )?;
+    pool.result()?;
+
+    // Without a salt, the threads stopped because of the limits before scoring any.
+    let mut best = best.into_inner().unwrap().ok_or_else(|| limits.error())?;
+    best.mined.attempts = attempts
+        .iter()
+        .map(|attempts| attempts.load(Ordering::Relaxed))
+        .sum();
+    Ok(best)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        errors::Create3GenerateSaltError,
+        optimize::{gas_saved_per_call, generate_salt_zeros, ZeroScore},
+        Address, CancellationToken, Create3Variant, SaltEncoding, SaltScheme, SearchLimits,
+    };
+
+    #[test]
+    fn should_score_zero_bytes() {
+        let runs = [
+            ("0x00000000219ab540356cBB839Cbe05303d7705Fa", 4, 4),
+            ("0x0000000000000000000000000000000000000000", 20, 20),
+            ("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045", 0, 0),
+            ("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA90000", 0, 2),
+            ("0x00dA6BF26964aF9D7e009e03E53415D37aA90000", 1, 4),
+        ];
+        for (address, leading, total) in runs.iter() {
+            let address: Address = address.parse().unwrap();
+            assert_eq!(ZeroScore::LeadingZeroBytes.score(&address), *leading);
+            assert_eq!(ZeroScore::TotalZeroBytes.score(&address), *total);
+            assert_eq!(gas_saved_per_call(&address), *total as u64 * 12);
+        }
+    }
+
+    #[test]
+    fn should_estimate_expected_attempts() {
+        let runs = [
+            (ZeroScore::LeadingZeroBytes, 0, 1.0),
+            (ZeroScore::LeadingZeroBytes, 2, 65536.0),
+            (ZeroScore::TotalZeroBytes, 0, 1.0),
+            (ZeroScore::TotalZeroBytes, 1, 13.28),
+            (ZeroScore::TotalZeroBytes, 20, 256f64.powi(20)),
+        ];
+        for (score, target, expected) in runs.iter() {
+            let attempts = score.expected_attempts(*target);
+            assert!(
+                (attempts / expected - 1.0).abs() < 1e-3,
+                "{} {}: {}",
+                score,
+                target,
+                attempts
+            );
+        }
+    }
+
+    #[test]
+    fn should_generate_until_target_score() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let limits = SearchLimits::new().with_timeout(Duration::from_secs(600));
+        let runs = [ZeroScore::LeadingZeroBytes, ZeroScore::TotalZeroBytes];
+
+        for score in runs.iter() {
+            let best =
+                generate_salt_zeros(deployer, "", *score, 1, &limits, 4, Create3Variant::Solady)
+                    .unwrap();
+            assert!(best.score >= 1);
+            assert_eq!(score.score(&best.mined.address), best.score);
+            assert_eq!(
+                crate::calc_addr(
+                    deployer,
+                    best.mined.preimage.as_bytes(),
+                    Create3Variant::Solady
+                ),
+                best.mined.address
+            );
+            assert_eq!(
+                best.gas_saved_per_call,
+                gas_saved_per_call(&best.mined.address)
+            );
+        }
+    }
+
+    #[test]
+    fn should_return_best_result_at_deadline() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let deadline = Instant::now() + Duration::from_millis(200);
+        let best = generate_salt_zeros(
+            deployer,
+            "testpfx_",
+            ZeroScore::TotalZeroBytes,
+            20,
+            &SearchLimits::new().with_deadline(deadline),
+            2,
+            Create3Variant::Solady,
+        )
+        .unwrap();
+
+        assert!(Instant::now() >= deadline);
+        assert!(best.mined.preimage.starts_with("testpfx_"));
+        assert_eq!(
+            ZeroScore::TotalZeroBytes.score(&best.mined.address),
+            best.score
+        );
+    }
+
+    #[test]
+    fn should_error_if_salt_prefix_cannot_be_encoded() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let scheme = SaltScheme::new(Create3Variant::Solady, SaltEncoding::Uint256);
+        assert_eq!(
+            generate_salt_zeros(
+                deployer,
+                "testpfx_",
+                ZeroScore::LeadingZeroBytes,
+                1,
+                &SearchLimits::default(),
+                1,
+                scheme
+            ),
+            Err(Create3GenerateSaltError::InvalidSaltPrefix)
+        );
+    }
+
+    #[test]
+    fn should_return_best_result_after_max_attempts() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let best = generate_salt_zeros(
+            deployer,
+            "",
+            ZeroScore::TotalZeroBytes,
+            20,
+            &SearchLimits::new().with_max_attempts(1000),
+            3,
+            Create3Variant::Solady,
+        )
+        .unwrap();
+
+        assert_eq!(best.mined.attempts, 1000);
+        assert_eq!(
+            ZeroScore::TotalZeroBytes.score(&best.mined.address),
+            best.score
+        );
+    }
+
+    #[test]
+    fn should_error_if_cancelled_before_scoring() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        let runs = [
+            (
+                SearchLimits::new().with_cancellation(cancelled),
+                Create3GenerateSaltError::Cancelled,
+            ),
+            (
+                SearchLimits::new().with_max_attempts(0),
+                Create3GenerateSaltError::Timeout,
+            ),
+        ];
+
+        for (limits, err) in runs {
+            assert_eq!(
+                generate_salt_zeros(
+                    deployer,
+                    "",
+                    ZeroScore::LeadingZeroBytes,
+                    20,
+                    &limits,
+                    2,
+                    Create3Variant::Solady
+                ),
+                Err(err)
+            );
+        }
+    }
+
+    #[test]
+    fn should_parse_scores() {
+        let runs = [ZeroScore::LeadingZeroBytes, ZeroScore::TotalZeroBytes];
+        for run in runs.iter() {
+            assert_eq!(run.to_string().parse::<ZeroScore>(), Ok(*run));
+        }
+    }
+}
@@ -0,0 +1,280 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{errors::Create3GenerateSaltError, Address};
+
+/// The number of nibbles in an address.
+const ADDRESS_NIBBLES: usize = Address::LEN * 2;
+
+/// An address pattern a mined salt has to produce.
+///
+/// A pattern is made of hex digits, `?` (any single nibble) and `*` (any number of nibbles), with an optional `0x`
+/// or `0X` prefix. Without a `*` the pattern is matched against the start of the address, so `dead` is a prefix,
+/// `*cafe` a suffix, `*beef*` a substring and `0000????????????????????????????????cafe` fixes single positions.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pattern {
+    /// The normalized pattern, always anchored at both ends of the address.
+    pattern: Vec<u8>,
+    /// Whether the letter case of the pattern has to match the EIP-55 checksummed address.
+    checksum: bool,
+}
+
+impl Pattern {
+    /// Parses a case-insensitive address pattern.
+    pub fn new(pattern: &str) -> Result<Self, Create3GenerateSaltError> {
+        let mut pattern = Pattern::new_checksum(pattern)?;
+        pattern.pattern.make_ascii_lowercase();
+        pattern.checksum = false;
+        Ok(pattern)
+    }
+
+    /// Parses an address pattern whose letter case has to match the EIP-55 checksummed address.
+    pub fn new_checksum(pattern: &str) -> Result<Self, Create3GenerateSaltError> {
+        let trimmed = pattern.trim();
+        let pattern = trimmed
+            .strip_prefix("0x")
+            .or_else(|| trimmed.strip_prefix("0X"))
+            .unwrap_or(trimmed);
+
+        // Every character before an invalid one is ASCII, so its byte index is its position.
+        let invalid = pattern
+            .char_indices()
+            .find(|(_, c)| !(c.is_ascii_hexdigit() || *c == '?' || *c == '*'));
+        if let Some((index, char)) = invalid {
+            return Err(Create3GenerateSaltError::InvalidPatternChar {
+                index: trimmed.len() - pattern.len() + index,
+                char,
+            });
+        } else if pattern.chars().filter(|c| *c != '*').count() > ADDRESS_NIBBLES {
+            return Err(Create3GenerateSaltError::PatternTooLong);
+        }
+
+        let mut pattern = pattern.as_bytes().to_vec();
+        // A pattern without `*` is a prefix.
+        if !pattern.contains(&b'*') {
+            pattern.push(b'*');
+        }
+        pattern.dedup_by(|a, b| *a == b'*' && *b == b'*');
+
+        Ok(Pattern {
+            pattern,
+            checksum: true,
+        })
+    }
+
+    /// Returns whether the address matches the pattern.
+    pub fn matches(&self, address: &Address) -> bool {
+        if self.checksum {
+            let hex = address.checksum_hex();
+            glob_match(&self.pattern, |i| hex[i])
+        } else {
+            // Only the nibbles the pattern gets to are turned into hex digits, so most addresses are rejected after
+            // looking at the first one.
+            glob_match(&self.pattern, |i| {
+                HEX_DIGITS[((address.0[i / 2] >> (4 * (1 - i % 2))) & 0x0f) as usize]
+            })
+        }
+    }
+
+    /// Returns whether the letter case of the pattern has to match the EIP-55 checksummed address.
+    pub fn is_checksum(&self) -> bool {
+        self.checksum
+    }
+
+    /// Returns the expected number of salts to test before one produces a matching address.
+    ///
+    /// Every hex digit matches one address in 16, and one in 32 for a letter whose case has to match. A pattern with
+    /// `*` can match at several positions, which are counted as independent, so this slightly underestimates short
+    /// repetitive patterns like `*aa*`.
+    pub fn expected_attempts(&self) -> f64 {
+        let fixed = self.pattern.iter().filter(|c| **c != b'*');
+        let odds: f64 = fixed
+            .clone()
+            .map(|c| match c {
+                b'?' => 1.0,
+                b'a'..=b'f' | b'A'..=b'F' if self.checksum => 32.0,
+                _ => 16.0,
+            })
+            .product();
+
+        // The nibbles not fixed by the pattern can be spread over the `*`s in `free + stars - 1 choose stars - 1` ways.
+        let free = (ADDRESS_NIBBLES - fixed.count()) as f64;
+        let stars = self.pattern.iter().filter(|c| **c == b'*').count() as f64;
+        let positions: f64 = (1..stars as u64)
+            .map(|k| (free + k as f64) / k as f64)
+            .product();
+
+        (odds / positions).max(1.0)
+    }
+
+    /// Returns the lowercase hex digits and `?` of a case-insensitive pattern that only constrains the start of the
+    /// address.
+    pub(crate) fn as_prefix(&self) -> Option<&[u8]> {
+        let (last, prefix) = self.pattern.split_last()?;
+        (!self.checksum && *last == b'*' && !prefix.contains(&b'*')).then_some(prefix)
+    }
+}
+
+/// The lowercase hex digits, indexed by nibble.
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Matches the hex digits of an address, returned by `digit`, against a pattern of literal bytes, `?` and `*`,
+/// anchored at both ends.
+fn glob_match(pattern: &[u8], digit: impl Fn(usize) -> u8) -> bool {
+    let (mut p, mut t) = (0, 0);
+    // The last `*` seen and the text position it is currently matched up to, to backtrack to on a mismatch.
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < ADDRESS_NIBBLES {
+        match pattern.get(p) {
+            Some(b'*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(c) if *c == b'?' || *c == digit(t) => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                Some((star, star_t)) => {
+                    backtrack = Some((star, star_t + 1));
+                    p = star + 1;
+                    t = star_t + 1;
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == b'*')
+}
+
+/// Formats the normalized pattern, e.g. `dead*`.
+impl Display for Pattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(std::str::from_utf8(&self.pattern).unwrap())
+    }
+}
+
+/// Parses a case-insensitive address pattern.
+impl FromStr for Pattern {
+    type Err = Create3GenerateSaltError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Pattern::new(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{errors::Create3GenerateSaltError, pattern::Pattern, Address};
+
+    #[test]
+    fn should_match_patterns() {
+        let address: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let runs = [
+            ("", true),
+            ("d8da", true),
+            ("0xD8DA", true),
+            ("0Xd8da", true),
+            ("d8db", false),
+            ("*6045", true),
+            ("*6046", false),
+            ("*9e03e5*", true),
+            ("*9e03f5*", false),
+            ("d8*45", true),
+            ("d8*46", false),
+            ("d8??6bf2*", true),
+            ("????????????????????????????????????6045", true),
+            ("d8da6bf26964af9d7eed9e03e53415d37aa96045", true),
+            ("d8da6bf26964af9d7eed9e03e53415d37aa9604?", true),
+            ("d8da6bf26964af9d7eed9e03e53415d37aa9604f", false),
+            ("**a**a**", true),
+            ("*a*a*a*a*a*", false),
+        ];
+        for (run, matches) in runs.iter() {
+            let pattern: Pattern = run.parse().unwrap();
+            assert_eq!(pattern.matches(&address), *matches, "{}", run);
+        }
+    }
+
+    #[test]
+    fn should_match_checksum_patterns() {
+        let address: Address = "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            .parse()
+            .unwrap();
+        let runs = [
+            ("d8dA", true),
+            ("d8da", false),
+            ("*aA96045", true),
+            ("*AA96045", false),
+            ("?8?A*", true),
+        ];
+        for (run, matches) in runs.iter() {
+            let pattern = Pattern::new_checksum(run).unwrap();
+            assert_eq!(pattern.matches(&address), *matches, "{}", run);
+        }
+    }
+
+    fn invalid_char(index: usize, char: char) -> Create3GenerateSaltError {
+        Create3GenerateSaltError::InvalidPatternChar { index, char }
+    }
+
+    #[test]
+    fn should_error_on_invalid_patterns() {
+        let runs = [
+            ("hey", invalid_char(0, 'h')),
+            ("0x0x12", invalid_char(3, 'x')),
+            (" 0Xab cd", invalid_char(4, ' ')),
+            ("12+", invalid_char(2, '+')),
+            ("dead€", invalid_char(4, '€')),
+            (
+                "0x00000000000000000000000000000000000000000",
+                Create3GenerateSaltError::PatternTooLong,
+            ),
+            (
+                "*?????????????????????????????????????????",
+                Create3GenerateSaltError::PatternTooLong,
+            ),
+        ];
+        for (run, err) in runs {
+            assert_eq!(run.parse::<Pattern>(), Err(err));
+        }
+    }
+
+    #[test]
+    fn should_estimate_expected_attempts() {
+        let runs = [
+            (Pattern::new("dead").unwrap(), 65536.0),
+            (Pattern::new("*dead").unwrap(), 65536.0),
+            (Pattern::new("de??ad").unwrap(), 65536.0),
+            (Pattern::new("*dead*").unwrap(), 65536.0 / 37.0),
+            (Pattern::new("").unwrap(), 1.0),
+            (Pattern::new_checksum("dEad").unwrap(), 65536.0 * 16.0),
+            (Pattern::new_checksum("0000").unwrap(), 65536.0),
+        ];
+        for (pattern, expected) in runs.iter() {
+            let attempts = pattern.expected_attempts();
+            assert!(
+                (attempts - expected).abs() < 1e-6,
+                "{}: {}",
+                pattern,
+                attempts
+            );
+        }
+    }
+
+    #[test]
+    fn should_normalize_patterns() {
+        let runs = [
+            ("0xDEAD", "dead*"),
+            ("*cafe", "*cafe"),
+            ("**be**ef", "*be*ef"),
+            ("", "*"),
+        ];
+        for (run, normalized) in runs.iter() {
+            assert_eq!(run.parse::<Pattern>().unwrap().to_string(), *normalized);
+        }
+    }
+}
@@ -0,0 +1,192 @@
+use std::{
+    panic::{self, AssertUnwindSafe},
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    thread,
+    time::Duration,
+};
+
+use crate::errors::Create3GenerateSaltError;
+
+/// How often a thread waiting for the workers checks on them. The last worker to finish wakes it up immediately.
+pub(crate) const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Returns the number of workers to run, where `0` means one per available core, or one per thread of the current
+/// rayon pool with the `rayon` feature.
+pub(crate) fn resolve_thread_count(thread_count: usize) -> usize {
+    match thread_count {
+        #[cfg(feature = "rayon")]
+        0 => rayon::current_num_threads(),
+        #[cfg(not(feature = "rayon"))]
+        0 => thread::available_parallelism().map_or(1, |n| n.get()),
+        n => n,
+    }
+}
+
+/// The workers of a search, which share a stop flag instead of a lock.
+///
+/// Every worker is given its index, so it can walk its own disjoint part of the search space. A worker that panics
+/// stops the others, and the panic is reported by `result` instead of unwinding into the caller.
+///
+/// The workers are scoped threads, or with the `rayon` feature jobs on the current rayon pool: the pool a search is
+/// started from with `ThreadPool::install`, or the global pool.
+pub(crate) struct Pool {
+    thread_count: usize,
+    stopped: AtomicBool,
+    panicked: AtomicBool,
+    running: AtomicUsize,
+    /// The thread waiting for the workers, which is unparked when the last worker finishes.
+    caller: thread::Thread,
+}
+
+impl Pool {
+    /// Creates a pool of `thread_count` workers, see `resolve_thread_count`, waited for by the current thread.
+    pub(crate) fn new(thread_count: usize) -> Self {
+        let thread_count = resolve_thread_count(thread_count);
+        Pool {
+            thread_count,
+            stopped: AtomicBool::new(false),
+            panicked: AtomicBool::new(false),
+            running: AtomicUsize::new(thread_count),
+            caller: thread::current(),
+        }
+    }
+
+    pub(crate) fn thread_count(&self) -> usize {
+        self.thread_count
+    }
+
+    /// Runs `worker` with the index of every worker, and `caller` on the calling thread while they run.
+    ///
+    /// # Returns
+    ///
+    /// The result of `caller`, once every worker has finished.
+    #[cfg(not(feature = "rayon"))]
+    pub(crate) fn run<W, C, R>(&self, worker: W, caller: C) -> R
+    where
+        W: Fn(usize) + Clone + Send,
+        C: FnOnce() -> R,
+    {
+        thread::scope(|scope| {
+            for thread in 0..self.thread_count {
+                let worker = worker.clone();
+                scope.spawn(move || self.work(&worker, thread));
+            }
+            caller()
+        })
+    }
+
+    /// Runs `worker` with the index of every worker, and `caller` on the calling thread while they run.
+    ///
+    /// # Returns
+    ///
+    /// The result of `caller`, once every worker has finished.
+    #[cfg(feature = "rayon")]
+    pub(crate) fn run<W, C, R>(&self, worker: W, caller: C) -> R
+    where
+        W: Fn(usize) + Clone + Send,
+        C: FnOnce() -> R,
+    {
+        rayon::in_place_scope(|scope| {
+            for thread in 0..self.thread_count {
+                let worker = worker.clone();
+                scope.spawn(move |_| self.work(&worker, thread));
+            }
+            caller()
+        })
+    }
+
+    fn work(&self, worker: &impl Fn(usize), thread: usize) {
+        if panic::catch_unwind(AssertUnwindSafe(|| worker(thread))).is_err() {
+            self.panicked.store(true, Ordering::Relaxed);
+            self.stop();
+        }
+        if self.running.fetch_sub(1, Ordering::Release) == 1 {
+            self.wake();
+        }
+    }
+
+    /// Blocks the calling thread for up to `timeout`, or until the last worker finishes.
+    ///
+    /// On a thread of a rayon pool, a pending job is run instead, so a search started from inside a pool that is too
+    /// small for all its workers still finishes. Workers therefore have to stop on their own, not only when the caller
+    /// tells them to.
+    pub(crate) fn park(&self, timeout: Duration) {
+        #[cfg(feature = "rayon")]
+        if rayon::current_thread_index().is_some()
+            && rayon::yield_now() == Some(rayon::Yield::Executed)
+        {
+            return;
+        }
+        thread::park_timeout(timeout);
+    }
+
+    /// Wakes up the thread waiting for the workers.
+    pub(crate) fn wake(&self) {
+        self.caller.unpark();
+    }
+
+    /// Asks every worker to stop.
+    pub(crate) fn stop(&self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+
+    pub(crate) fn is_stopped(&self) -> bool {
+        self.stopped.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn is_finished(&self) -> bool {
+        self.running.load(Ordering::Acquire) == 0
+    }
+
+    /// Returns an error if a worker panicked.
+    pub(crate) fn result(&self) -> Result<(), Create3GenerateSaltError> {
+        match self.panicked.load(Ordering::Relaxed) {
+            true => Err(Create3GenerateSaltError::WorkerPanicked),
+            false => Ok(()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    use crate::{
+        errors::Create3GenerateSaltError,
+        pool::{resolve_thread_count, Pool},
+    };
+
+    #[test]
+    fn should_run_every_worker() {
+        let pool = Pool::new(4);
+        let ran = AtomicUsize::new(0);
+        pool.run(
+            |thread| {
+                ran.fetch_add(1 << thread, Ordering::Relaxed);
+            },
+            || {},
+        );
+        assert_eq!(ran.into_inner(), 0b1111);
+        assert!(pool.is_finished());
+        assert_eq!(pool.result(), Ok(()));
+        assert!(resolve_thread_count(0) >= 1);
+    }
+
+    #[test]
+    fn should_report_a_panicking_worker() {
+        let pool = Pool::new(3);
+        let ran = AtomicUsize::new(0);
+        pool.run(
+            |thread| {
+                if thread == 1 {
+                    panic!("worker failed");
+                }
+                ran.fetch_add(1, Ordering::Relaxed);
+            },
+            || {},
+        );
+        assert_eq!(ran.into_inner(), 2);
+        assert!(pool.is_stopped());
+        assert_eq!(pool.result(), Err(Create3GenerateSaltError::WorkerPanicked));
+    }
+}
@@ -0,0 +1,296 @@
+use std::{
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Mutex,
+    },
+    time::Instant,
+};
+
+use rand::Rng;
+
+use crate::{
+    errors::Create3GenerateSaltError,
+    hasher::{AddressHasher, BATCH},
+    pool::Pool,
+    search::{report, watch},
+    Address, MinedSalt, Pattern, Salt, SaltEncoding, SaltScheme, SearchLimits,
+};
+
+/// The maximum number of leading salt bytes that can be fixed. The last 8 bytes are always searched.
+pub const MAX_FIXED_BYTES: usize = 24;
+
+/// Builds the raw salt tested for `counter`: `[fixed] ++ [filler] ++ [counter]`.
+///
+/// The filler is random per search, so repeated searches find different salts, and the counter takes the last 8
+/// bytes.
+fn counter_salt(fixed: &[u8], filler: &[u8; MAX_FIXED_BYTES], counter: u64) -> Salt {
+    let mut salt = [0u8; 32];
+    salt[..fixed.len()].copy_from_slice(fixed);
+    salt[fixed.len()..MAX_FIXED_BYTES].copy_from_slice(&filler[fixed.len()..]);
+    salt[MAX_FIXED_BYTES..].copy_from_slice(&counter.to_be_bytes());
+    Salt(salt)
+}
+
+/// Generates a raw bytes32 salt for a given address pattern, for factories that take an arbitrary salt.
+///
+/// The salt is searched directly, without hashing a salt string first, so every attempt costs one keccak less than
+/// `generate_salt`.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `fixed` - The leading bytes of the salt that are kept as is, e.g. the caller of a factory that checks
+///   `salt[0:20]`. At most `MAX_FIXED_BYTES` long.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with the `msg.sender` the
+///   factory binds into the salt. The salt encoding is not used, as the salt is passed to the factory as is.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error, and how it reports its progress.
+///   `SearchLimits::default()` never gives up.
+///
+/// # Returns
+///
+/// The generated salt, whose `salt` is the raw salt to pass to the factory and whose preimage is its hex string, with
+/// the resulting address and how it was found.
+pub fn generate_raw_salt(
+    deployer: &Address,
+    fixed: &[u8],
+    pattern: &str,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    generate_raw_salt_multithread(deployer, fixed, pattern, 1, scheme, limits)
+}
+
+/// Generates a raw bytes32 salt for a given address pattern by using multiple threads, for factories that take an
+/// arbitrary salt.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `fixed` - The leading bytes of the salt that are kept as is, e.g. the caller of a factory that checks
+///   `salt[0:20]`. At most `MAX_FIXED_BYTES` long.
+/// * `pattern` - The pattern the resulting address should match, e.g. a hex prefix like `dead` (see `Pattern`).
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for, optionally with the `msg.sender` the
+///   factory binds into the salt. The salt encoding is not used, as the salt is passed to the factory as is.
+/// * `limits` - When the search gives up with a `Timeout` or `Cancelled` error, and how it reports its progress.
+///   `SearchLimits::default()` never gives up.
+///
+/// # Returns
+///
+/// The generated salt, whose `salt` is the raw salt to pass to the factory and whose preimage is its hex string, with
+/// the resulting address and how it was found.
+pub fn generate_raw_salt_multithread(
+    deployer: &Address,
+    fixed: &[u8],
+    pattern: &str,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    // The salt is passed to the factory as is, which is what the raw encoding of its hex string gives.
+    let scheme = SaltScheme {
+        encoding: SaltEncoding::Raw,
+        ..scheme.into()
+    };
+    let pattern = Pattern::new(pattern)?;
+    if fixed.len() > MAX_FIXED_BYTES {
+        return Err(Create3GenerateSaltError::TooManyFixedBytes);
+    }
+    let filler: [u8; MAX_FIXED_BYTES] = rand::thread_rng().gen();
+
+    let pool = Pool::new(thread_count);
+    let thread_count = pool.thread_count() as u64;
+    let attempts: Vec<AtomicU64> = (0..thread_count).map(|_| AtomicU64::new(0)).collect();
+    let end = limits.end(0);
+    let started = Instant::now();
+    let found: Mutex<Option<MinedSalt>> = Mutex::new(None);
+
+    pool.run(
+        |thread| {
+            let hasher = AddressHasher::new(deployer, &scheme);
+
+            // Every thread walks its own disjoint counters, a batch at a time, and stops on its own once a limit is
+            // hit.
+            let mut counter = thread as u64;
+            while !pool.is_stopped()
+                && counter < end
+                && !limits.is_cancelled()
+                && !limits.is_expired()
+            {
+                let counters: [u64; BATCH] =
+                    std::array::from_fn(|i| counter + i as u64 * thread_count);
+                counter += BATCH as u64 * thread_count;
+                let salts = counters.map(|counter| counter_salt(fixed, &filler, counter));
+                let vanity_addrs = hasher.calc_addrs(&salts);
+                let tested = counters.iter().filter(|counter| **counter < end).count();
+                attempts[thread].fetch_add(tested as u64, Ordering::Relaxed);
+
+                for i in 0..BATCH {
+                    if counters[i] < end && pattern.matches(&vanity_addrs[i]) {
+                        found.lock().unwrap().get_or_insert_with(|| MinedSalt {
+                            preimage: salts[i].to_string(),
+                            salt: salts[i],
+                            address: vanity_addrs[i],
+                            deployer: *deployer,
+                            scheme,
+                            pattern: pattern.to_string(),
+                            checksum: false,
+                            // Counted once the search stops.
+                            attempts: 0,
+                            elapsed: started.elapsed(),
+                        });
+                        pool.stop();
+                        break;
+                    }
+                }
+            }
+        },
+        || {
+            let report = || report(&attempts, started, pattern.expected_attempts());
+            watch(&pool, limits, report, || Ok(()))
+        },
+    )?;
+    pool.result()?;
+
+    // Without a salt, the threads stopped because of the limits.
+    let mut mined = found.into_inner().unwrap().ok_or_else(|| limits.error())?;
+    mined.attempts = attempts
+        .iter()
+        .map(|attempts| attempts.load(Ordering::Relaxed))
+        .sum();
+    Ok(mined)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::{Duration, Instant};
+
+    use crate::{
+        errors::Create3GenerateSaltError,
+        raw::{generate_raw_salt, generate_raw_salt_multithread, MAX_FIXED_BYTES},
+        Address, CancellationToken, Create3Variant, Pattern, SaltScheme, SearchLimits,
+        SenderBinding,
+    };
+
+    #[test]
+    fn should_generate_raw_salt_with_fixed_bytes() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let caller: Address = "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        let mut caller_and_flag = caller.0.to_vec();
+        caller_and_flag.push(0x00);
+        let runs: [(&[u8], &str, SaltScheme); 4] = [
+            (&[], "ab", Create3Variant::Solady.into()),
+            (&caller.0, "*cd", Create3Variant::Solady.into()),
+            (&caller_and_flag, "1?2", Create3Variant::Solmate.into()),
+            (
+                &[0x42; MAX_FIXED_BYTES],
+                "0",
+                SaltScheme::from(Create3Variant::ZeframLou)
+                    .with_sender(SenderBinding::Packed(caller)),
+            ),
+        ];
+
+        for (fixed, pattern, scheme) in runs.iter() {
+            let mined = generate_raw_salt_multithread(
+                deployer,
+                fixed,
+                pattern,
+                4,
+                *scheme,
+                &SearchLimits::default(),
+            )
+            .unwrap();
+            assert_eq!(&mined.salt.0[..fixed.len()], *fixed);
+            assert_eq!(
+                mined.scheme.encoding.encode(&mined.preimage),
+                Ok(mined.salt)
+            );
+            let addr = scheme.calc_addr_with_bytes(deployer, &mined.salt);
+            assert_eq!(mined.address, addr);
+            assert!(Pattern::new(pattern).unwrap().matches(&addr));
+        }
+    }
+
+    #[test]
+    fn should_generate_different_raw_salts() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let limits = SearchLimits::default();
+        let first = generate_raw_salt(deployer, &[], "0", Create3Variant::Solady, &limits).unwrap();
+        let second =
+            generate_raw_salt(deployer, &[], "0", Create3Variant::Solady, &limits).unwrap();
+        assert_ne!(first.salt, second.salt);
+    }
+
+    #[test]
+    fn should_error_on_invalid_raw_search() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        let runs: [(&[u8], &str, Create3GenerateSaltError); 2] = [
+            (
+                &[0u8; MAX_FIXED_BYTES + 1],
+                "0",
+                Create3GenerateSaltError::TooManyFixedBytes,
+            ),
+            (
+                &[],
+                "xyz",
+                Create3GenerateSaltError::InvalidPatternChar {
+                    index: 0,
+                    char: 'x',
+                },
+            ),
+        ];
+        for (fixed, pattern, err) in runs {
+            assert_eq!(
+                generate_raw_salt(
+                    deployer,
+                    fixed,
+                    pattern,
+                    Create3Variant::Solady,
+                    &SearchLimits::default()
+                ),
+                Err(err)
+            );
+        }
+    }
+
+    #[test]
+    fn should_give_up_on_an_impossible_pattern() {
+        let deployer: &Address = &"5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap();
+        // 40 nibbles will not be found by any of these searches.
+        let pattern = "0".repeat(40);
+        let cancelled = CancellationToken::new();
+        cancelled.cancel();
+        let runs = [
+            (
+                SearchLimits::new().with_max_attempts(1000),
+                Create3GenerateSaltError::Timeout,
+            ),
+            (
+                SearchLimits::new().with_timeout(Duration::from_millis(50)),
+                Create3GenerateSaltError::Timeout,
+            ),
+            (
+                SearchLimits::new().with_cancellation(cancelled),
+                Create3GenerateSaltError::Cancelled,
+            ),
+        ];
+
+        for (limits, err) in runs {
+            let start = Instant::now();
+            assert_eq!(
+                generate_raw_salt_multithread(
+                    deployer,
+                    &[],
+                    &pattern,
+                    3,
+                    Create3Variant::Solady,
+                    &limits
+                ),
+                Err(err)
+            );
+            assert!(start.elapsed() < Duration::from_secs(10));
+        }
+    }
+}
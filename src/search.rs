@@ -0,0 +1,943 @@
+use std::{
+    fmt::Display,
+    fs, io,
+    path::Path,
+    str::FromStr,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex, PoisonError,
+    },
+    time::{Duration, Instant},
+};
+
+use crate::{
+    errors::Create3GenerateSaltError,
+    hasher::{AddressHasher, BATCH},
+    pool::{Pool, POLL_INTERVAL},
+    Address, MinedSalt, Pattern, Salt, SaltEncoding, SaltScheme, SenderBinding,
+};
+
+/// How often `generate_salt_resumable` writes its checkpoint file.
+pub const CHECKPOINT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// Mixes the seed into a counter, so that every seed walks through the salts in a different order.
+///
+/// This is a bijection on `u64` (the splitmix64 finalizer), so distinct counters never give the same salt.
+pub(crate) fn mix(seed: u64, counter: u64) -> u64 {
+    let mut z = counter ^ seed;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+/// Returns the salt string tested for `counter` in the search space of `seed`.
+pub(crate) fn candidate(
+    encoding: SaltEncoding,
+    salt_prefix: &str,
+    seed: u64,
+    counter: u64,
+) -> String {
+    encoding.counter_salt(salt_prefix, mix(seed, counter))
+}
+
+/// Like `candidate`, but overwrites `salt`, so that a search loop can reuse one buffer.
+pub(crate) fn write_candidate(
+    encoding: SaltEncoding,
+    salt_prefix: &str,
+    seed: u64,
+    counter: u64,
+    salt: &mut String,
+) {
+    encoding.write_counter_salt(salt_prefix, mix(seed, counter), salt)
+}
+
+/// Encodes a batch of salt strings, hashing them several at a time for the `keccak256` encoding.
+pub(crate) fn encode_candidates(
+    hasher: &AddressHasher,
+    encoding: SaltEncoding,
+    salts: &[String; BATCH],
+) -> [Salt; BATCH] {
+    match encoding {
+        SaltEncoding::Keccak256 => hasher
+            .keccak256_batch(&salts.each_ref().map(|salt| salt.as_bytes()))
+            .map(Salt),
+        _ => salts.each_ref().map(|salt| encoding.encode(salt).unwrap()),
+    }
+}
+
+/// A handle to cancel a running salt search from another thread.
+///
+/// Clones share the same flag, so a clone can be kept by the caller while the search holds another.
+#[derive(Debug, Clone, Default)]
+pub struct CancellationToken(pub(crate) Arc<AtomicBool>);
+
+impl CancellationToken {
+    /// Creates a token that has not been cancelled.
+    pub fn new() -> Self {
+        CancellationToken::default()
+    }
+
+    /// Cancels every search holding a clone of this token.
+    pub fn cancel(&self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns whether the token has been cancelled.
+    pub fn is_cancelled(&self) -> bool {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+/// Cancels when the flag is set, e.g. by a signal handler.
+impl From<Arc<AtomicBool>> for CancellationToken {
+    fn from(flag: Arc<AtomicBool>) -> Self {
+        CancellationToken(flag)
+    }
+}
+
+/// A snapshot of a running salt search, passed to a `ProgressHook`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Progress {
+    /// The number of salts tested so far.
+    pub attempts: u64,
+    /// The time since the search started.
+    pub elapsed: Duration,
+    /// The salts tested per second by every thread, on average since the search started.
+    pub thread_hashrates: Vec<f64>,
+    /// The salts tested per second by all threads together.
+    pub hashrate: f64,
+    /// The expected number of salts to test before one matches, see `Pattern::expected_attempts`, or before one
+    /// reaches the target score of the zero-byte optimizer, see `ZeroScore::expected_attempts`.
+    pub expected_attempts: f64,
+}
+
+impl Progress {
+    /// Returns the expected time until a salt is found, or `None` before any salt has been tested.
+    ///
+    /// Every salt matches with the same probability, so the expected time remaining does not shrink as the search
+    /// goes on: an unlucky search is not any closer to a match.
+    pub fn eta(&self) -> Option<Duration> {
+        if self.hashrate <= 0.0 {
+            return None;
+        }
+        Duration::try_from_secs_f64(self.expected_attempts / self.hashrate).ok()
+    }
+}
+
+/// A callback that is called with the `Progress` of a salt search at a fixed interval, on the calling thread.
+#[derive(Clone)]
+pub struct ProgressHook {
+    interval: Duration,
+    callback: Arc<dyn Fn(&Progress) + Send + Sync>,
+}
+
+impl ProgressHook {
+    /// Creates a hook that calls `callback` every `interval` while a search is running.
+    pub fn new(interval: Duration, callback: impl Fn(&Progress) + Send + Sync + 'static) -> Self {
+        ProgressHook {
+            interval,
+            callback: Arc::new(callback),
+        }
+    }
+}
+
+impl std::fmt::Debug for ProgressHook {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ProgressHook")
+            .field("interval", &self.interval)
+            .finish_non_exhaustive()
+    }
+}
+
+/// When a salt search gives up, and how it reports its progress. The default never gives up and reports nothing.
+#[derive(Debug, Clone, Default)]
+pub struct SearchLimits {
+    /// Stops the search with `Create3GenerateSaltError::Cancelled` once cancelled.
+    pub cancellation: Option<CancellationToken>,
+    /// Stops the search with `Create3GenerateSaltError::Timeout` after testing this many salts.
+    pub max_attempts: Option<u64>,
+    /// Stops the search with `Create3GenerateSaltError::Timeout` at this point in time.
+    pub deadline: Option<Instant>,
+    /// Reports the progress of the search while it runs.
+    pub progress: Option<ProgressHook>,
+}
+
+impl SearchLimits {
+    /// Returns limits that never give up.
+    pub fn new() -> Self {
+        SearchLimits::default()
+    }
+
+    /// Returns the limits with a cancellation token.
+    pub fn with_cancellation(self, cancellation: CancellationToken) -> Self {
+        SearchLimits {
+            cancellation: Some(cancellation),
+            ..self
+        }
+    }
+
+    /// Returns the limits with a maximum number of salts to test.
+    pub fn with_max_attempts(self, max_attempts: u64) -> Self {
+        SearchLimits {
+            max_attempts: Some(max_attempts),
+            ..self
+        }
+    }
+
+    /// Returns the limits with a deadline.
+    pub fn with_deadline(self, deadline: Instant) -> Self {
+        SearchLimits {
+            deadline: Some(deadline),
+            ..self
+        }
+    }
+
+    /// Returns the limits with a deadline `timeout` from now.
+    pub fn with_timeout(self, timeout: Duration) -> Self {
+        self.with_deadline(Instant::now() + timeout)
+    }
+
+    /// Returns the limits with a progress hook.
+    pub fn with_progress(self, progress: ProgressHook) -> Self {
+        SearchLimits {
+            progress: Some(progress),
+            ..self
+        }
+    }
+
+    pub(crate) fn is_cancelled(&self) -> bool {
+        self.cancellation
+            .as_ref()
+            .is_some_and(CancellationToken::is_cancelled)
+    }
+
+    pub(crate) fn is_expired(&self) -> bool {
+        self.deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+    }
+
+    /// Returns the counter at which a search starting at `next` stops because of `max_attempts`.
+    pub(crate) fn end(&self, next: u64) -> u64 {
+        match self.max_attempts {
+            Some(max_attempts) => next.saturating_add(max_attempts),
+            None => u64::MAX,
+        }
+    }
+
+    /// Returns the error of a search that gave up before finding a salt.
+    pub(crate) fn error(&self) -> Create3GenerateSaltError {
+        match self.is_cancelled() {
+            true => Create3GenerateSaltError::Cancelled,
+            false => Create3GenerateSaltError::Timeout,
+        }
+    }
+}
+
+/// Waits for the workers of `pool` on the calling thread, calling `poll` and the progress hook of `limits` until they
+/// have finished.
+///
+/// The workers are stopped if `poll` fails. They have to stop on their own once `limits` give up, see `Pool::park`.
+pub(crate) fn watch(
+    pool: &Pool,
+    limits: &SearchLimits,
+    report: impl Fn() -> Progress,
+    mut poll: impl FnMut() -> Result<(), Create3GenerateSaltError>,
+) -> Result<(), Create3GenerateSaltError> {
+    let mut last_report = Instant::now();
+    while !pool.is_finished() {
+        poll().inspect_err(|_| pool.stop())?;
+        if let Some(hook) = &limits.progress {
+            if last_report.elapsed() >= hook.interval {
+                (hook.callback)(&report());
+                last_report = Instant::now();
+            }
+        }
+        pool.park(POLL_INTERVAL);
+    }
+    Ok(())
+}
+
+/// Returns the progress of a search that started at `started`, from the number of salts each worker has tested.
+pub(crate) fn report(attempts: &[AtomicU64], started: Instant, expected_attempts: f64) -> Progress {
+    let elapsed = started.elapsed();
+    let secs = elapsed.as_secs_f64().max(f64::MIN_POSITIVE);
+    let attempts: Vec<u64> = attempts
+        .iter()
+        .map(|attempts| attempts.load(Ordering::Relaxed))
+        .collect();
+    let total: u64 = attempts.iter().sum();
+
+    Progress {
+        attempts: total,
+        elapsed,
+        thread_hashrates: attempts.iter().map(|n| *n as f64 / secs).collect(),
+        hashrate: total as f64 / secs,
+        expected_attempts,
+    }
+}
+
+/// The state of a deterministic salt search, which can be saved to and restored from a checkpoint file.
+///
+/// The search tests the salt of every counter from `next` upwards. The same seed always tests the same salts in the
+/// same order and finds the match with the lowest counter, whatever the number of threads.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchState {
+    /// The create3 deployer address.
+    pub deployer: Address,
+    /// The prefix of every generated salt.
+    pub salt_prefix: String,
+    /// The pattern the resulting address should match (see `Pattern`).
+    pub pattern: String,
+    /// Whether the letter case of the pattern has to match the EIP-55 checksummed address.
+    pub checksum: bool,
+    /// The CREATE3 factory or CREATE2 init code the salt is mined for.
+    pub scheme: SaltScheme,
+    /// The seed choosing the order in which salts are tested.
+    pub seed: u64,
+    /// Every counter below this has been tested.
+    pub next: u64,
+}
+
+impl SearchState {
+    /// Creates the state of a new search, with a random seed if none is given.
+    pub fn new(
+        deployer: &Address,
+        salt_prefix: &str,
+        pattern: &str,
+        checksum: bool,
+        scheme: impl Into<SaltScheme>,
+        seed: Option<u64>,
+    ) -> Self {
+        SearchState {
+            deployer: *deployer,
+            salt_prefix: salt_prefix.to_owned(),
+            pattern: pattern.to_owned(),
+            checksum,
+            scheme: scheme.into(),
+            seed: seed.unwrap_or_else(rand::random),
+            next: 0,
+        }
+    }
+
+    /// Returns the salt string tested for `counter`.
+    pub fn candidate(&self, counter: u64) -> String {
+        candidate(self.scheme.encoding, &self.salt_prefix, self.seed, counter)
+    }
+
+    /// Writes the state to a checkpoint file, replacing it atomically.
+    pub fn save(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        // `str::lines` drops a `\r` before a line break, so a loaded state would differ from the saved one.
+        let is_line_break = |c: char| c == '\n' || c == '\r';
+        if self.salt_prefix.contains(is_line_break) || self.pattern.contains(is_line_break) {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "the salt prefix and pattern cannot contain line breaks",
+            ));
+        }
+
+        // Write a temporary file first, so an interrupted write never corrupts the previous checkpoint.
+        let path = path.as_ref();
+        let mut tmp = path.as_os_str().to_owned();
+        tmp.push(".tmp");
+        fs::write(&tmp, self.to_string())?;
+        fs::rename(&tmp, path)
+    }
+
+    /// Reads the state from a checkpoint file.
+    pub fn load(path: impl AsRef<Path>) -> io::Result<Self> {
+        fs::read_to_string(path)?
+            .parse()
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+}
+
+/// Formats the state as the `key=value` lines of a checkpoint file.
+impl Display for SearchState {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "deployer={}", self.deployer)?;
+        writeln!(f, "salt_prefix={}", self.salt_prefix)?;
+        writeln!(f, "pattern={}", self.pattern)?;
+        writeln!(f, "checksum={}", self.checksum)?;
+        writeln!(f, "mode={}", self.scheme.mode)?;
+        writeln!(f, "encoding={}", self.scheme.encoding)?;
+        if let Some(sender) = self.scheme.sender {
+            writeln!(f, "sender={}", sender)?;
+        }
+        writeln!(f, "seed={}", self.seed)?;
+        writeln!(f, "next={}", self.next)
+    }
+}
+
+/// Parses the `key=value` lines of a checkpoint file.
+impl FromStr for SearchState {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let value = |key: &str| {
+            s.lines()
+                .find_map(|line| line.strip_prefix(key)?.strip_prefix('='))
+                .ok_or_else(|| format!("missing '{}'", key))
+        };
+        let invalid = |key: &str| format!("invalid '{}'", key);
+
+        let sender: Option<SenderBinding> = match value("sender") {
+            Ok(sender) => Some(sender.parse()?),
+            Err(_) => None,
+        };
+        let mut scheme = SaltScheme::new(
+            value("mode")?.parse::<crate::DeployMode>()?,
+            value("encoding")?.parse()?,
+        );
+        scheme.sender = sender;
+
+        Ok(SearchState {
+            deployer: value("deployer")?
+                .parse()
+                .map_err(|_| invalid("deployer"))?,
+            salt_prefix: value("salt_prefix")?.to_owned(),
+            pattern: value("pattern")?.to_owned(),
+            checksum: value("checksum")?
+                .parse()
+                .map_err(|_| invalid("checksum"))?,
+            scheme,
+            seed: value("seed")?.parse().map_err(|_| invalid("seed"))?,
+            next: value("next")?.parse().map_err(|_| invalid("next"))?,
+        })
+    }
+}
+
+/// Continues a deterministic salt search until a salt is found or `stop` is set, e.g. from a Ctrl-C handler.
+///
+/// # Arguments
+///
+/// * `state` - The search to continue. Its `next` counter is advanced to reflect the progress made.
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `stop` - Stops the search when set, keeping the progress made in `state`.
+/// * `checkpoint` - A file the state is written to every `CHECKPOINT_INTERVAL` and when the search ends.
+/// * `progress` - Reports the progress of the search while it runs.
+///
+/// # Returns
+///
+/// The generated salt, with the salts tested since the search was continued as its `attempts`, or `None` if the search
+/// was stopped first.
+pub fn generate_salt_resumable(
+    state: &mut SearchState,
+    thread_count: usize,
+    stop: &AtomicBool,
+    checkpoint: Option<&Path>,
+    progress: Option<&ProgressHook>,
+) -> Result<Option<MinedSalt>, Create3GenerateSaltError> {
+    let pattern = if state.checksum {
+        Pattern::new_checksum(&state.pattern)?
+    } else {
+        Pattern::new(&state.pattern)?
+    };
+    state
+        .scheme
+        .encoding
+        .validate_salt_prefix(&state.salt_prefix)?;
+
+    let save = |state: &SearchState| match checkpoint {
+        Some(path) => state
+            .save(path)
+            .map_err(|_| Create3GenerateSaltError::CheckpointFailed),
+        None => Ok(()),
+    };
+
+    let limits = SearchLimits {
+        progress: progress.cloned(),
+        ..SearchLimits::default()
+    };
+    let mut search = Search::new(state, &pattern, thread_count, limits);
+    search.stop = Some(stop);
+    let mut last_checkpoint = Instant::now();
+    search.run(|| {
+        if last_checkpoint.elapsed() >= CHECKPOINT_INTERVAL {
+            state.next = search.progress();
+            save(state)?;
+            last_checkpoint = Instant::now();
+        }
+        Ok(())
+    })?;
+
+    let progress = search.progress();
+    let found = search
+        .found
+        .into_inner()
+        .unwrap_or_else(PoisonError::into_inner);
+    let result = search.pool.result();
+    state.next = match (&found, &result) {
+        // Every counter below the match has been tested, so a resumed search continues after it.
+        (Some((counter, _)), Ok(())) => counter + 1,
+        _ => progress,
+    };
+    save(state)?;
+    result?;
+
+    Ok(found.map(|(_, mined)| mined))
+}
+
+/// Searches salts for a pattern without a checkpoint, with a random seed, until a salt is found or a limit is hit.
+pub(crate) fn search_once(
+    deployer: &Address,
+    salt_prefix: &str,
+    pattern: &Pattern,
+    thread_count: usize,
+    scheme: SaltScheme,
+    limits: &SearchLimits,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    search_until(
+        deployer,
+        salt_prefix,
+        pattern,
+        thread_count,
+        scheme,
+        limits,
+        &AtomicBool::new(false),
+    )
+}
+
+/// Like `search_once`, but also stops with `Create3GenerateSaltError::Cancelled` when `stop` is set.
+pub(crate) fn search_until(
+    deployer: &Address,
+    salt_prefix: &str,
+    pattern: &Pattern,
+    thread_count: usize,
+    scheme: SaltScheme,
+    limits: &SearchLimits,
+    stop: &AtomicBool,
+) -> Result<MinedSalt, Create3GenerateSaltError> {
+    scheme.encoding.validate_salt_prefix(salt_prefix)?;
+    let state = SearchState {
+        deployer: *deployer,
+        salt_prefix: salt_prefix.to_owned(),
+        pattern: pattern.to_string(),
+        checksum: pattern.is_checksum(),
+        scheme,
+        seed: rand::random(),
+        next: 0,
+    };
+
+    let mut search = Search::new(&state, pattern, thread_count, limits.clone());
+    search.stop = Some(stop);
+    search.run(|| Ok(()))?;
+    search.pool.result()?;
+
+    match search.found.into_inner().unwrap() {
+        Some((_, mined)) => Ok(mined),
+        None if limits.is_cancelled() || stop.load(Ordering::Relaxed) => {
+            Err(Create3GenerateSaltError::Cancelled)
+        }
+        None => Err(Create3GenerateSaltError::Timeout),
+    }
+}
+
+/// Measures how many salts per second a search for the scheme tests, by searching for an impossible pattern for
+/// `duration`.
+///
+/// # Arguments
+///
+/// * `deployer` - The create3 deployer address.
+/// * `salt_prefix` - The prefix of every generated salt.
+/// * `thread_count` - The number of threads to create when calculating the address. `0` uses one per available
+///   core.
+/// * `scheme` - The CREATE3 factory or CREATE2 init code the salt is mined for.
+/// * `duration` - How long to measure for.
+///
+/// # Returns
+///
+/// The number of salts tested per second by all threads together, or an error if the salt prefix cannot be used with
+/// the encoding.
+pub fn measure_hashrate(
+    deployer: &Address,
+    salt_prefix: &str,
+    thread_count: usize,
+    scheme: impl Into<SaltScheme>,
+    duration: Duration,
+) -> Result<f64, Create3GenerateSaltError> {
+    let scheme = scheme.into();
+    scheme.encoding.validate_salt_prefix(salt_prefix)?;
+    // A full address is never matched in practice, so the search runs until the deadline.
+    let pattern = Pattern::new(&"0".repeat(40))?;
+    let state = SearchState::new(deployer, salt_prefix, "", false, scheme, None);
+
+    let search = Search::new(
+        &state,
+        &pattern,
+        thread_count,
+        SearchLimits::new().with_timeout(duration),
+    );
+    search.run(|| Ok(()))?;
+    search.pool.result()?;
+    Ok(search.report().hashrate)
+}
+
+/// The shared state of the threads of a counter-based search.
+///
+/// Thread `t` of `n` tests the counters `start + t`, `start + t + n`, `start + t + 2n`, ... in order, so every counter
+/// below `start + n * min(rounds)` has been tested.
+struct Search<'a> {
+    state: SearchState,
+    pattern: &'a Pattern,
+    pool: Pool,
+    thread_count: u64,
+    /// Stops the search when set, like a cancelled `limits.cancellation`.
+    stop: Option<&'a AtomicBool>,
+    /// The number of counters each thread has tested.
+    rounds: Vec<AtomicU64>,
+    /// The lowest matching counter found so far, or `u64::MAX`.
+    best: AtomicU64,
+    found: Mutex<Option<(u64, MinedSalt)>>,
+    limits: SearchLimits,
+    /// The counter at which the search stops because of `limits.max_attempts`.
+    end: u64,
+    started: Instant,
+}
+
+impl<'a> Search<'a> {
+    fn new(
+        state: &SearchState,
+        pattern: &'a Pattern,
+        thread_count: usize,
+        limits: SearchLimits,
+    ) -> Self {
+        let pool = Pool::new(thread_count);
+        let thread_count = pool.thread_count() as u64;
+        let end = limits.end(state.next);
+        Search {
+            state: state.clone(),
+            pattern,
+            pool,
+            thread_count,
+            stop: None,
+            rounds: (0..thread_count).map(|_| AtomicU64::new(0)).collect(),
+            best: AtomicU64::new(u64::MAX),
+            found: Mutex::new(None),
+            limits,
+            end,
+            started: Instant::now(),
+        }
+    }
+
+    /// Runs the workers, and waits for them on the calling thread, calling `poll` and reporting progress until they
+    /// have finished.
+    ///
+    /// The search is stopped if `poll` fails.
+    fn run(
+        &self,
+        poll: impl FnMut() -> Result<(), Create3GenerateSaltError>,
+    ) -> Result<(), Create3GenerateSaltError> {
+        self.pool.run(
+            |thread| self.work(thread),
+            || watch(&self.pool, &self.limits, || self.report(), poll),
+        )
+    }
+
+    fn work(&self, thread: usize) {
+        let state = &self.state;
+        let encoding = state.scheme.encoding;
+        let rounds = &self.rounds[thread];
+        let thread = thread as u64;
+        let hasher = AddressHasher::new(&state.deployer, &state.scheme);
+        let mut salts: [String; BATCH] = Default::default();
+
+        // The counters are tested a batch of rounds at a time, so the hasher can hash several salts at once.
+        for batch in 0.. {
+            let round = batch * BATCH as u64;
+            let counter = state.next + thread + round * self.thread_count;
+            // A thread stops once a lower counter matched, so the lowest match wins whatever the thread count.
+            if counter >= self.best.load(Ordering::Relaxed)
+                || counter >= self.end
+                || self.pool.is_stopped()
+                || self.stop.is_some_and(|stop| stop.load(Ordering::Relaxed))
+                || self.limits.is_cancelled()
+                || self.limits.is_expired()
+            {
+                return;
+            }
+
+            let counters: [u64; BATCH] =
+                std::array::from_fn(|i| counter + i as u64 * self.thread_count);
+            for (salt, counter) in salts.iter_mut().zip(counters) {
+                write_candidate(encoding, &state.salt_prefix, state.seed, counter, salt);
+            }
+            let salt_bytes = encode_candidates(&hasher, encoding, &salts);
+            let vanity_addrs = hasher.calc_addrs(&salt_bytes);
+
+            for i in 0..BATCH {
+                if counters[i] < self.end && self.pattern.matches(&vanity_addrs[i]) {
+                    let mut found = self.found.lock().unwrap();
+                    if found.as_ref().is_none_or(|(best, _)| counters[i] < *best) {
+                        self.best.store(counters[i], Ordering::Relaxed);
+                        let mined = MinedSalt {
+                            preimage: salts[i].clone(),
+                            salt: salt_bytes[i],
+                            address: vanity_addrs[i],
+                            deployer: state.deployer,
+                            scheme: state.scheme,
+                            pattern: self.pattern.to_string(),
+                            checksum: self.pattern.is_checksum(),
+                            attempts: counters[i] - state.next + 1,
+                            elapsed: self.started.elapsed(),
+                        };
+                        *found = Some((counters[i], mined));
+                    }
+                    return;
+                }
+            }
+            rounds.store(round + BATCH as u64, Ordering::Relaxed);
+        }
+    }
+
+    /// Returns the progress of the search since it started.
+    fn report(&self) -> Progress {
+        report(&self.rounds, self.started, self.pattern.expected_attempts())
+    }
+
+    /// Returns the counter below which every salt has been tested.
+    fn progress(&self) -> u64 {
+        let min_rounds = self
+            .rounds
+            .iter()
+            .map(|rounds| rounds.load(Ordering::Relaxed))
+            .min()
+            .unwrap();
+        let progress = self.state.next + min_rounds * self.thread_count;
+        progress.min(self.best.load(Ordering::Relaxed))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::HashSet, sync::atomic::AtomicBool, time::Duration};
+
+    use crate::{
+        calc_addr_with_bytes,
+        errors::Create3GenerateSaltError,
+        search::{generate_salt_resumable, measure_hashrate, mix, SearchState},
+        Address, Create3Variant, DeployMode, InitCodeHash, MinedSalt, SaltEncoding, SaltScheme,
+        SenderBinding,
+    };
+
+    fn deployer() -> Address {
+        "5e17b14ADd6c386305A32928F985b29bbA34Eff5".parse().unwrap()
+    }
+
+    #[test]
+    fn mix_should_not_repeat() {
+        let mixed: HashSet<u64> = (0..10_000).map(|counter| mix(42, counter)).collect();
+        assert_eq!(mixed.len(), 10_000);
+        assert_ne!(mix(1, 0), mix(2, 0));
+    }
+
+    #[test]
+    fn should_reproduce_with_a_seed_whatever_the_thread_count() {
+        let stop = AtomicBool::new(false);
+        let mut results = Vec::new();
+
+        for thread_count in [1, 3, 6] {
+            let mut state = SearchState::new(
+                &deployer(),
+                "pfx_",
+                "ab",
+                false,
+                Create3Variant::Solady,
+                Some(7),
+            );
+            let mined = generate_salt_resumable(&mut state, thread_count, &stop, None, None)
+                .unwrap()
+                .unwrap();
+            assert_eq!(
+                calc_addr_with_bytes(&deployer(), &mined.salt, Create3Variant::Solady),
+                mined.address
+            );
+            assert!(hex::encode(mined.address).starts_with("ab"));
+            assert_eq!(state.candidate(state.next - 1), mined.preimage);
+            // The search started at counter 0, so the match was the `next`th salt in its order.
+            assert_eq!(mined.attempts, state.next);
+            results.push((mined.preimage, state.next));
+        }
+
+        assert!(results.windows(2).all(|pair| pair[0] == pair[1]));
+        // Pinned, so the std and `rayon` workers have to find the same salt.
+        assert_eq!(results[0], ("pfx_BQ4fPKjosYJ".to_string(), 83));
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn should_search_inside_a_rayon_pool() {
+        let stop = AtomicBool::new(false);
+
+        // A pool smaller than the search runs the remaining workers once its threads are free.
+        for (pool_threads, thread_count) in [(1, 3), (3, 3), (2, 0)] {
+            let pool = rayon::ThreadPoolBuilder::new()
+                .num_threads(pool_threads)
+                .build()
+                .unwrap();
+            let mut state = SearchState::new(
+                &deployer(),
+                "pfx_",
+                "ab",
+                false,
+                Create3Variant::Solady,
+                Some(7),
+            );
+            let MinedSalt {
+                preimage: salt,
+                salt: _,
+                ..
+            } = pool
+                .install(|| generate_salt_resumable(&mut state, thread_count, &stop, None, None))
+                .unwrap()
+                .unwrap();
+            assert_eq!((salt, state.next), ("pfx_BQ4fPKjosYJ".to_string(), 83));
+        }
+    }
+
+    #[test]
+    fn should_resume_after_the_last_match() {
+        let stop = AtomicBool::new(false);
+        let mut state =
+            SearchState::new(&deployer(), "", "0", false, Create3Variant::Solady, Some(1));
+
+        let MinedSalt {
+            preimage: first,
+            salt: _,
+            ..
+        } = generate_salt_resumable(&mut state, 2, &stop, None, None)
+            .unwrap()
+            .unwrap();
+        let MinedSalt {
+            preimage: second,
+            salt: _,
+            ..
+        } = generate_salt_resumable(&mut state, 2, &stop, None, None)
+            .unwrap()
+            .unwrap();
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn should_stop_and_keep_progress() {
+        let stop = AtomicBool::new(true);
+        // 40 nibbles will not be found before the search notices the stop flag.
+        let pattern = "0".repeat(40);
+        let mut state = SearchState::new(
+            &deployer(),
+            "",
+            &pattern,
+            false,
+            Create3Variant::Solady,
+            None,
+        );
+
+        let path = std::env::temp_dir().join(format!("create3-stop-{}.checkpoint", state.seed));
+        assert_eq!(
+            generate_salt_resumable(&mut state, 2, &stop, Some(&path), None),
+            Ok(None)
+        );
+        assert_eq!(SearchState::load(&path).unwrap(), state);
+        std::fs::remove_file(path).unwrap();
+    }
+
+    #[test]
+    fn should_save_and_load_checkpoints() {
+        let sender: Address = "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045".parse().unwrap();
+        let runs = [
+            SaltScheme::from(Create3Variant::Solady),
+            SaltScheme::new(
+                DeployMode::Create2 {
+                    init_code_hash: InitCodeHash([3u8; 32]),
+                },
+                SaltEncoding::Uint256,
+            ),
+            SaltScheme::new(Create3Variant::ZeframLou, SaltEncoding::RightPadded)
+                .with_sender(SenderBinding::Packed(sender)),
+        ];
+
+        for (i, scheme) in runs.iter().enumerate() {
+            let mut state = SearchState::new(&deployer(), "a=b", "*Be?f", true, *scheme, None);
+            state.next = 1234;
+
+            let path =
+                std::env::temp_dir().join(format!("create3-{}-{}.checkpoint", state.seed, i));
+            state.save(&path).unwrap();
+            assert_eq!(SearchState::load(&path).unwrap(), state);
+            std::fs::remove_file(path).unwrap();
+        }
+
+        // A line break would not survive the round trip, so it is rejected instead of saved.
+        let runs = [
+            ("pfx\r", "0"),
+            ("pfx\n", "0"),
+            ("pfx", "ab\r"),
+            ("\r\npfx", "0"),
+        ];
+        for (i, (salt_prefix, pattern)) in runs.iter().enumerate() {
+            let state = SearchState::new(
+                &deployer(),
+                salt_prefix,
+                pattern,
+                false,
+                Create3Variant::Solady,
+                None,
+            );
+            let path =
+                std::env::temp_dir().join(format!("create3-{}-{}.checkpoint", state.seed, i));
+            assert_eq!(
+                state.save(&path).unwrap_err().kind(),
+                std::io::ErrorKind::InvalidInput
+            );
+            assert!(!path.exists());
+        }
+
+        assert!("deployer=0x00".parse::<SearchState>().is_err());
+    }
+
+    #[test]
+    fn should_measure_hashrate() {
+        let duration = Duration::from_millis(200);
+        let hashrate = measure_hashrate(&deployer(), "pfx_", 2, Create3Variant::Solady, duration);
+        assert!(hashrate.unwrap() > 0.0);
+
+        assert_eq!(
+            measure_hashrate(
+                &deployer(),
+                "testpfx_",
+                1,
+                SaltScheme::new(Create3Variant::Solady, SaltEncoding::Uint256),
+                duration
+            ),
+            Err(Create3GenerateSaltError::InvalidSaltPrefix)
+        );
+    }
+
+    #[test]
+    fn should_error_on_an_invalid_search() {
+        let stop = AtomicBool::new(false);
+        let mut state =
+            SearchState::new(&deployer(), "", "xyz", false, Create3Variant::Solady, None);
+        assert_eq!(
+            generate_salt_resumable(&mut state, 1, &stop, None, None),
+            Err(Create3GenerateSaltError::InvalidPatternChar {
+                index: 0,
+                char: 'x'
+            })
+        );
+
+        let mut state = SearchState::new(
+            &deployer(),
+            "pfx\n",
+            "0",
+            false,
+            Create3Variant::Solady,
+            None,
+        );
+        let path = std::env::temp_dir().join(format!("create3-{}.checkpoint", state.seed));
+        assert_eq!(
+            generate_salt_resumable(&mut state, 1, &stop, Some(&path), None),
+            Err(Create3GenerateSaltError::CheckpointFailed)
+        );
+    }
+}
@@ -0,0 +1,356 @@
+#[cfg(target_arch = "x86_64")]
+use std::arch::x86_64::*;
+
+use sha3::{Digest, Keccak256};
+
+/// The number of messages hashed per call.
+pub(crate) const LANES: usize = 8;
+
+/// The keccak256 rate in bytes. Messages shorter than this fit in a single block.
+const RATE: usize = 136;
+
+/// The round constants of the iota step.
+const RC: [u64; 24] = [
+    0x0000000000000001,
+    0x0000000000008082,
+    0x800000000000808a,
+    0x8000000080008000,
+    0x000000000000808b,
+    0x0000000080000001,
+    0x8000000080008081,
+    0x8000000000008009,
+    0x000000000000008a,
+    0x0000000000000088,
+    0x0000000080008009,
+    0x000000008000000a,
+    0x000000008000808b,
+    0x800000000000008b,
+    0x8000000000008089,
+    0x8000000000008003,
+    0x8000000000008002,
+    0x8000000000000080,
+    0x000000000000800a,
+    0x800000008000000a,
+    0x8000000080008081,
+    0x8000000000008080,
+    0x0000000080000001,
+    0x8000000080008008,
+];
+
+/// The rotation offsets of the rho step, in the order the pi step visits the lanes.
+const RHO: [u32; 24] = [
+    1, 3, 6, 10, 15, 21, 28, 36, 45, 55, 2, 14, 27, 41, 56, 8, 25, 43, 62, 18, 39, 61, 20, 44,
+];
+
+/// The lanes visited by the pi step, starting from lane 1.
+const PI: [usize; 24] = [
+    10, 7, 11, 17, 18, 3, 5, 16, 8, 21, 24, 4, 15, 23, 19, 13, 12, 2, 20, 14, 22, 9, 6, 1,
+];
+
+/// A 64-bit keccak lane, or a vector of lanes of different states that are permuted together.
+trait Lane: Copy {
+    fn xor(self, other: Self) -> Self;
+    fn rotl(self, n: u32) -> Self;
+    /// `self ^ (!b & c)`.
+    fn chi(self, b: Self, c: Self) -> Self;
+    fn xor_constant(self, constant: u64) -> Self;
+}
+
+impl Lane for u64 {
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        self ^ other
+    }
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        self.rotate_left(n)
+    }
+
+    #[inline(always)]
+    fn chi(self, b: Self, c: Self) -> Self {
+        self ^ (!b & c)
+    }
+
+    #[inline(always)]
+    fn xor_constant(self, constant: u64) -> Self {
+        self ^ constant
+    }
+}
+
+/// The keccak-f[1600] permutation, for one state or a vector of states.
+#[inline(always)]
+fn keccak_f<L: Lane>(a: &mut [L; 25]) {
+    for rc in RC {
+        // theta
+        let mut c = [a[0]; 5];
+        for x in 0..5 {
+            c[x] = a[x]
+                .xor(a[x + 5])
+                .xor(a[x + 10])
+                .xor(a[x + 15])
+                .xor(a[x + 20]);
+        }
+        for x in 0..5 {
+            let d = c[(x + 4) % 5].xor(c[(x + 1) % 5].rotl(1));
+            for y in 0..5 {
+                a[x + 5 * y] = a[x + 5 * y].xor(d);
+            }
+        }
+
+        // rho and pi
+        let mut last = a[1];
+        for (j, rho) in PI.iter().zip(RHO) {
+            let next = a[*j];
+            a[*j] = last.rotl(rho);
+            last = next;
+        }
+
+        // chi
+        for y in 0..5 {
+            let row = [
+                a[5 * y],
+                a[5 * y + 1],
+                a[5 * y + 2],
+                a[5 * y + 3],
+                a[5 * y + 4],
+            ];
+            for x in 0..5 {
+                a[5 * y + x] = row[x].chi(row[(x + 1) % 5], row[(x + 2) % 5]);
+            }
+        }
+
+        // iota
+        a[0] = a[0].xor_constant(rc);
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy)]
+struct Avx2Lane(__m256i);
+
+#[cfg(target_arch = "x86_64")]
+impl Lane for Avx2Lane {
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        unsafe { Avx2Lane(_mm256_xor_si256(self.0, other.0)) }
+    }
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        unsafe {
+            let left = _mm256_sll_epi64(self.0, _mm_cvtsi32_si128(n as i32));
+            let right = _mm256_srl_epi64(self.0, _mm_cvtsi32_si128(64 - n as i32));
+            Avx2Lane(_mm256_or_si256(left, right))
+        }
+    }
+
+    #[inline(always)]
+    fn chi(self, b: Self, c: Self) -> Self {
+        unsafe { Avx2Lane(_mm256_xor_si256(self.0, _mm256_andnot_si256(b.0, c.0))) }
+    }
+
+    #[inline(always)]
+    fn xor_constant(self, constant: u64) -> Self {
+        unsafe {
+            Avx2Lane(_mm256_xor_si256(
+                self.0,
+                _mm256_set1_epi64x(constant as i64),
+            ))
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[derive(Clone, Copy)]
+struct Avx512Lane(__m512i);
+
+#[cfg(target_arch = "x86_64")]
+impl Lane for Avx512Lane {
+    #[inline(always)]
+    fn xor(self, other: Self) -> Self {
+        unsafe { Avx512Lane(_mm512_xor_si512(self.0, other.0)) }
+    }
+
+    #[inline(always)]
+    fn rotl(self, n: u32) -> Self {
+        unsafe { Avx512Lane(_mm512_rolv_epi64(self.0, _mm512_set1_epi64(n as i64))) }
+    }
+
+    #[inline(always)]
+    fn chi(self, b: Self, c: Self) -> Self {
+        // 0xd2 is the truth table of a ^ (!b & c).
+        unsafe { Avx512Lane(_mm512_ternarylogic_epi64::<0xd2>(self.0, b.0, c.0)) }
+    }
+
+    #[inline(always)]
+    fn xor_constant(self, constant: u64) -> Self {
+        unsafe { Avx512Lane(_mm512_xor_si512(self.0, _mm512_set1_epi64(constant as i64))) }
+    }
+}
+
+/// The states of all lanes, stored word by word so that the same word of every state can be loaded as one vector.
+type States = [[u64; LANES]; 25];
+
+/// Permutes 4 states at a time with AVX2.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn permute_avx2(states: &mut States) {
+    for half in 0..LANES / 4 {
+        let mut a = [Avx2Lane(_mm256_setzero_si256()); 25];
+        for (lane, words) in a.iter_mut().zip(states.iter()) {
+            lane.0 = _mm256_loadu_si256(words[half * 4..].as_ptr() as *const __m256i);
+        }
+        keccak_f(&mut a);
+        for (lane, words) in a.iter().zip(states.iter_mut()) {
+            _mm256_storeu_si256(words[half * 4..].as_mut_ptr() as *mut __m256i, lane.0);
+        }
+    }
+}
+
+/// Permutes all 8 states at once with AVX-512.
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn permute_avx512(states: &mut States) {
+    let mut a = [Avx512Lane(_mm512_setzero_si512()); 25];
+    for (lane, words) in a.iter_mut().zip(states.iter()) {
+        lane.0 = _mm512_loadu_si512(words.as_ptr() as *const __m512i);
+    }
+    keccak_f(&mut a);
+    for (lane, words) in a.iter().zip(states.iter_mut()) {
+        _mm512_storeu_si512(words.as_mut_ptr() as *mut __m512i, lane.0);
+    }
+}
+
+/// Permutes every state on its own.
+fn permute_scalar(states: &mut States) {
+    for lane in 0..LANES {
+        let mut a: [u64; 25] = std::array::from_fn(|i| states[i][lane]);
+        keccak_f(&mut a);
+        for (words, word) in states.iter_mut().zip(a) {
+            words[lane] = word;
+        }
+    }
+}
+
+/// A multi-lane keccak256 that hashes several short messages per call, for the mining loops.
+///
+/// On x86_64 the lanes are permuted with AVX-512 (8 lanes) or AVX2 (4 lanes) when the CPU supports it. Otherwise every
+/// lane is permuted on its own with the same scalar keccak-f[1600].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum Backend {
+    Scalar,
+    #[cfg(target_arch = "x86_64")]
+    Avx2,
+    #[cfg(target_arch = "x86_64")]
+    Avx512,
+}
+
+impl Backend {
+    /// Returns the fastest backend the CPU supports.
+    pub(crate) fn detect() -> Self {
+        #[cfg(target_arch = "x86_64")]
+        {
+            if is_x86_feature_detected!("avx512f") {
+                return Backend::Avx512;
+            } else if is_x86_feature_detected!("avx2") {
+                return Backend::Avx2;
+            }
+        }
+        Backend::Scalar
+    }
+
+    /// Returns whether the CPU supports the backend.
+    #[cfg(test)]
+    fn is_supported(&self) -> bool {
+        match self {
+            Backend::Scalar => true,
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => is_x86_feature_detected!("avx2"),
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => is_x86_feature_detected!("avx512f"),
+        }
+    }
+
+    /// Computes the keccak256 hash of every message.
+    ///
+    /// Messages that do not fit in a single block are hashed with `sha3` instead.
+    pub(crate) fn keccak256(&self, messages: &[&[u8]; LANES]) -> [[u8; 32]; LANES] {
+        let mut states: States = [[0u64; LANES]; 25];
+        for (lane, message) in messages.iter().enumerate() {
+            if message.len() >= RATE {
+                continue;
+            }
+
+            // Pad the message to a full block, with the keccak (not SHA-3) domain byte.
+            let mut block = [0u8; RATE];
+            block[..message.len()].copy_from_slice(message);
+            block[message.len()] ^= 0x01;
+            block[RATE - 1] ^= 0x80;
+            for (words, bytes) in states.iter_mut().zip(block.chunks_exact(8)) {
+                words[lane] = u64::from_le_bytes(bytes.try_into().unwrap());
+            }
+        }
+
+        match self {
+            Backend::Scalar => permute_scalar(&mut states),
+            // Safety: `detect` only returns the backends the CPU supports.
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx2 => unsafe { permute_avx2(&mut states) },
+            #[cfg(target_arch = "x86_64")]
+            Backend::Avx512 => unsafe { permute_avx512(&mut states) },
+        }
+
+        let mut hashes = [[0u8; 32]; LANES];
+        for (lane, hash) in hashes.iter_mut().enumerate() {
+            if messages[lane].len() >= RATE {
+                *hash = Keccak256::digest(messages[lane]).into();
+                continue;
+            }
+            for (bytes, words) in hash.chunks_exact_mut(8).zip(states.iter()) {
+                bytes.copy_from_slice(&words[lane].to_le_bytes());
+            }
+        }
+        hashes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use rand::Rng;
+    use sha3::{Digest, Keccak256};
+
+    use crate::simd::{Backend, LANES, RATE};
+
+    #[test]
+    fn every_backend_should_match_the_scalar_path() {
+        let mut backends = vec![Backend::Scalar];
+        #[cfg(target_arch = "x86_64")]
+        backends.extend([Backend::Avx2, Backend::Avx512]);
+
+        let mut rng = rand::thread_rng();
+        for round in 0..200 {
+            // Cover every length up to and past a block, including empty messages.
+            let messages: Vec<Vec<u8>> = (0..LANES)
+                .map(|lane| {
+                    let len = (round * LANES + lane) % (RATE + 20);
+                    (0..len).map(|_| rng.gen()).collect()
+                })
+                .collect();
+            let messages: [&[u8]; LANES] = std::array::from_fn(|lane| messages[lane].as_slice());
+
+            let scalar = Backend::Scalar.keccak256(&messages);
+            for (message, hash) in messages.iter().zip(scalar.iter()) {
+                assert_eq!(*hash, <[u8; 32]>::from(Keccak256::digest(message)));
+            }
+            for backend in backends.iter().filter(|backend| backend.is_supported()) {
+                assert_eq!(backend.keccak256(&messages), scalar, "{:?}", backend);
+            }
+        }
+    }
+
+    #[test]
+    fn should_detect_a_supported_backend() {
+        assert!(Backend::detect().is_supported());
+    }
+}
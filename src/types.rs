@@ -0,0 +1,264 @@
+use std::{fmt::Display, str::FromStr};
+
+use sha3::{Digest, Keccak256};
+
+use crate::errors::Create3ParseError;
+
+macro_rules! fixed_bytes {
+    ($(#[$attr:meta])* $name:ident, $len:literal) => {
+        $(#[$attr])*
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Default)]
+        pub struct $name(pub [u8; $len]);
+
+        impl $name {
+            /// The length of the value in bytes.
+            pub const LEN: usize = $len;
+
+            /// Creates a value from a byte slice, failing if it is not exactly `LEN` bytes long.
+            pub fn from_slice(bytes: &[u8]) -> Result<Self, Create3ParseError> {
+                bytes
+                    .try_into()
+                    .map($name)
+                    .map_err(|_| Create3ParseError::InvalidLength)
+            }
+
+            /// Returns the underlying bytes.
+            pub fn as_bytes(&self) -> &[u8; $len] {
+                &self.0
+            }
+        }
+
+        impl From<[u8; $len]> for $name {
+            fn from(bytes: [u8; $len]) -> Self {
+                $name(bytes)
+            }
+        }
+
+        impl From<$name> for [u8; $len] {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl TryFrom<&[u8]> for $name {
+            type Error = Create3ParseError;
+
+            fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+                $name::from_slice(bytes)
+            }
+        }
+
+        impl AsRef<[u8]> for $name {
+            fn as_ref(&self) -> &[u8] {
+                &self.0
+            }
+        }
+
+        /// Parses a hex encoded string with an optional `0x` prefix.
+        impl FromStr for $name {
+            type Err = Create3ParseError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let s = s.trim();
+                let s = s.strip_prefix("0x").unwrap_or(s);
+                if !s.chars().all(|c| c.is_ascii_hexdigit()) {
+                    return Err(Create3ParseError::NotHexEncoded);
+                } else if s.len() != $len * 2 {
+                    return Err(Create3ParseError::InvalidLength);
+                }
+
+                let mut bytes = [0u8; $len];
+                hex::decode_to_slice(s, &mut bytes).map_err(|_| Create3ParseError::NotHexEncoded)?;
+                Ok($name(bytes))
+            }
+        }
+    };
+}
+
+fixed_bytes!(
+    /// A 20-byte EVM address.
+    Address,
+    20
+);
+
+fixed_bytes!(
+    /// A 32-byte salt used as the `CREATE2` salt input.
+    Salt,
+    32
+);
+
+fixed_bytes!(
+    /// A keccak256 hash of contract init code.
+    InitCodeHash,
+    32
+);
+
+impl Address {
+    /// Returns the EIP-55 checksummed hex encoding of the address, with a `0x` prefix.
+    pub fn to_checksum(&self) -> String {
+        let hex = self.checksum_hex();
+        format!("0x{}", std::str::from_utf8(&hex).unwrap())
+    }
+
+    /// Returns the EIP-55 checksummed hex digits of the address without a `0x` prefix, without allocating.
+    pub(crate) fn checksum_hex(&self) -> [u8; 40] {
+        let mut hex = [0u8; 40];
+        hex::encode_to_slice(self.0, &mut hex).unwrap();
+        let hash = Keccak256::digest(hex);
+
+        // A letter is uppercased if the matching nibble of keccak256(lowercase address) is >= 8.
+        for (i, c) in hex.iter_mut().enumerate() {
+            let nibble = (hash[i / 2] >> (4 * (1 - i % 2))) & 0x0f;
+            if nibble >= 8 {
+                c.make_ascii_uppercase();
+            }
+        }
+        hex
+    }
+
+    /// Parses a hex encoded address with an optional `0x` prefix and validates its EIP-55 checksum.
+    ///
+    /// All-lowercase and all-uppercase inputs carry no checksum and are accepted as is.
+    pub fn from_str_checksummed(s: &str) -> Result<Self, Create3ParseError> {
+        let address: Address = s.parse()?;
+
+        let s = s.trim();
+        let s = s.strip_prefix("0x").unwrap_or(s);
+        let is_mixed_case =
+            s.chars().any(|c| c.is_ascii_lowercase()) && s.chars().any(|c| c.is_ascii_uppercase());
+        if is_mixed_case && address.to_checksum()[2..] != *s {
+            return Err(Create3ParseError::InvalidChecksum);
+        }
+
+        Ok(address)
+    }
+}
+
+/// Formats the address with its EIP-55 checksum.
+impl Display for Address {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_checksum())
+    }
+}
+
+impl Display for Salt {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+impl Display for InitCodeHash {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "0x{}", hex::encode(self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        errors::Create3ParseError,
+        types::{Address, InitCodeHash, Salt},
+    };
+
+    #[test]
+    fn should_parse_address_with_and_without_0x() {
+        let runs = [
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            "d8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            "  0xd8da6bf26964af9d7eed9e03e53415d37aa96045\n",
+        ];
+        for run in runs.iter() {
+            let address: Address = run.parse().unwrap();
+            assert_eq!(
+                address.to_string(),
+                "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045"
+            );
+            assert_eq!(address.to_string().parse::<Address>(), Ok(address));
+        }
+    }
+
+    #[test]
+    fn should_error_on_malformed_input() {
+        let runs = [
+            (
+                "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA9604",
+                Create3ParseError::InvalidLength,
+            ),
+            (
+                "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA960450",
+                Create3ParseError::InvalidLength,
+            ),
+            (
+                "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA9604g",
+                Create3ParseError::NotHexEncoded,
+            ),
+            (
+                "0x0xd8dA6BF26964aF9D7eEd9e03E53415D37aA960",
+                Create3ParseError::NotHexEncoded,
+            ),
+            ("", Create3ParseError::InvalidLength),
+        ];
+        for (run, err) in runs.iter() {
+            assert_eq!(run.parse::<Address>(), Err(*err));
+        }
+        assert_eq!(
+            Address::from_slice(&[0u8; 21]),
+            Err(Create3ParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn should_parse_salt_and_init_code_hash() {
+        let hex = "0x3ac225168df54212a25c1c01fd35bebfea408fdac2e31ddd6f80a4bbf9a5f1cb";
+        let salt: Salt = hex.parse().unwrap();
+        assert_eq!(salt.to_string(), hex);
+        assert_eq!(Salt::from_slice(salt.as_bytes()), Ok(salt));
+
+        let hash: InitCodeHash = hex.parse().unwrap();
+        assert_eq!(hash.0, salt.0);
+        assert_eq!(
+            "0x3ac2".parse::<InitCodeHash>(),
+            Err(Create3ParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn should_checksum_address() {
+        // https://eips.ethereum.org/EIPS/eip-55#test-cases
+        let runs = [
+            "0x52908400098527886E0F7030069857D2E4169EE7",
+            "0x8617E340B3D01FA5F11F306F4090FD50E238070D",
+            "0xde709f2102306220921060314715629080e2fb77",
+            "0x27b1fdb04752bbc536007a920d24acb045561c26",
+            "0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed",
+            "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359",
+            "0xdbF03B407c01E7cD3CBea99509d93f8DDDC8C6FB",
+            "0xD1220A0cf47c7B9Be7A2E6BA89F429762e7b9aDb",
+        ];
+        for run in runs.iter() {
+            let address: Address = run.to_lowercase().parse().unwrap();
+            assert_eq!(address.to_checksum(), *run);
+            assert_eq!(Address::from_str_checksummed(run), Ok(address));
+        }
+    }
+
+    #[test]
+    fn should_validate_mixed_case_checksum() {
+        let runs = [
+            "0xd8dA6BF26964aF9D7eEd9e03E53415D37aA96045",
+            "d8da6bf26964af9d7eed9e03e53415d37aa96045",
+            "D8DA6BF26964AF9D7EED9E03E53415D37AA96045",
+        ];
+        for run in runs.iter() {
+            assert!(Address::from_str_checksummed(run).is_ok());
+        }
+        assert_eq!(
+            Address::from_str_checksummed("0xd8Da6BF26964aF9D7eEd9e03E53415D37aA96045"),
+            Err(Create3ParseError::InvalidChecksum)
+        );
+        assert_eq!(
+            Address::from_str_checksummed("0xd8dA6BF26964aF9D7eEd9e03E53415D37aA9604"),
+            Err(Create3ParseError::InvalidLength)
+        );
+    }
+}
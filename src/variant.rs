@@ -0,0 +1,242 @@
+use std::{fmt::Display, str::FromStr};
+
+use sha3::{Digest, Keccak256};
+
+use crate::{calc_addr_with_bytes, calc_create2_addr, Address, InitCodeHash, Salt};
+
+/// keccak256 of the minimal CREATE3 proxy `0x67363d3d37363d34f03d5260086018f3`.
+///
+/// The same proxy is used by Solady, Solmate (and therefore ZeframLou's factory), 0xSequence and CreateX.
+pub const KECCAK256_PROXY_CHILD_BYTECODE: InitCodeHash = InitCodeHash([
+    33, 195, 93, 190, 27, 52, 74, 36, 136, 207, 51, 33, 214, 206, 84, 47, 142, 159, 48, 85, 68,
+    255, 9, 228, 153, 58, 98, 49, 154, 73, 124, 31,
+]);
+
+/// The CREATE3 factory implementation a contract is deployed through.
+///
+/// Every variant deploys a proxy with `CREATE2` (`0xff ++ deployer ++ salt ++ keccak256(proxy)`) and lets the
+/// proxy deploy the contract with `CREATE` at nonce 1. They differ in the proxy init code that is hashed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Create3Variant {
+    /// Solady's `CREATE3` library.
+    #[default]
+    Solady,
+    /// Solmate's `CREATE3` library.
+    Solmate,
+    /// ZeframLou's `CREATE3Factory`, built on top of Solmate.
+    ZeframLou,
+    /// 0xSequence's `Create3` library.
+    Sequence,
+    /// CreateX's `deployCreate3`.
+    CreateX,
+    /// Axelar's `Create3`, which deploys through the `CreateDeploy` helper contract instead of a minimal proxy.
+    ///
+    /// The helper's creation code depends on the compiler settings it was built with, so its hash has to be
+    /// supplied.
+    Axelar { create_deploy_hash: InitCodeHash },
+    /// Any other factory following the same layout with its own proxy init code hash.
+    Custom { proxy_init_code_hash: InitCodeHash },
+}
+
+impl Create3Variant {
+    /// Creates a custom variant from the proxy init code deployed by the factory.
+    pub fn from_proxy_init_code(proxy_init_code: &[u8]) -> Self {
+        Create3Variant::Custom {
+            proxy_init_code_hash: InitCodeHash(Keccak256::digest(proxy_init_code).into()),
+        }
+    }
+
+    /// Returns the keccak256 hash of the proxy init code deployed with `CREATE2`.
+    pub fn proxy_init_code_hash(&self) -> InitCodeHash {
+        match self {
+            Create3Variant::Solady
+            | Create3Variant::Solmate
+            | Create3Variant::ZeframLou
+            | Create3Variant::Sequence
+            | Create3Variant::CreateX => KECCAK256_PROXY_CHILD_BYTECODE,
+            Create3Variant::Axelar { create_deploy_hash } => *create_deploy_hash,
+            Create3Variant::Custom {
+                proxy_init_code_hash,
+            } => *proxy_init_code_hash,
+        }
+    }
+}
+
+impl Display for Create3Variant {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Create3Variant::Solady => f.write_str("solady"),
+            Create3Variant::Solmate => f.write_str("solmate"),
+            Create3Variant::ZeframLou => f.write_str("zeframlou"),
+            Create3Variant::Sequence => f.write_str("sequence"),
+            Create3Variant::CreateX => f.write_str("createx"),
+            Create3Variant::Axelar { create_deploy_hash } => {
+                write!(f, "axelar:{}", create_deploy_hash)
+            }
+            Create3Variant::Custom {
+                proxy_init_code_hash,
+            } => write!(f, "custom:{}", proxy_init_code_hash),
+        }
+    }
+}
+
+/// Parses the preset names (`solady`, `solmate`, `zeframlou`, `sequence`, `createx`) as well as
+/// `axelar:<hash>` and `custom:<hash>` with a hex encoded 32-byte hash.
+impl FromStr for Create3Variant {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        let (name, hash) = match s.split_once(':') {
+            Some((name, hash)) => (name, Some(hash)),
+            None => (s, None),
+        };
+
+        let parse_hash = || -> Result<InitCodeHash, String> {
+            let hash = hash.ok_or_else(|| format!("variant '{}' requires a 32-byte hash", name))?;
+            hash.parse().map_err(|e| format!("'{}': {}", hash, e))
+        };
+
+        match name.to_lowercase().as_str() {
+            "solady" => Ok(Create3Variant::Solady),
+            "solmate" => Ok(Create3Variant::Solmate),
+            "zeframlou" => Ok(Create3Variant::ZeframLou),
+            "sequence" | "0xsequence" => Ok(Create3Variant::Sequence),
+            "createx" => Ok(Create3Variant::CreateX),
+            "axelar" => Ok(Create3Variant::Axelar {
+                create_deploy_hash: parse_hash()?,
+            }),
+            "custom" => Ok(Create3Variant::Custom {
+                proxy_init_code_hash: parse_hash()?,
+            }),
+            _ => Err(format!("unknown CREATE3 variant '{}'", name)),
+        }
+    }
+}
+
+/// How a mined salt is turned into an address.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DeployMode {
+    /// The salt is used with `CREATE2` to deploy the given init code directly.
+    Create2 { init_code_hash: InitCodeHash },
+    /// The salt is used with the given CREATE3 factory.
+    Create3(Create3Variant),
+}
+
+impl DeployMode {
+    /// Calculates the address for a salt that is digested by keccak256 before use.
+    pub fn calc_addr(&self, deployer: &Address, salt: &[u8]) -> Address {
+        self.calc_addr_with_bytes(deployer, &Salt(Keccak256::digest(salt).into()))
+    }
+
+    /// Calculates the address for a salt that is directly used as the salt input.
+    pub fn calc_addr_with_bytes(&self, deployer: &Address, salt: &Salt) -> Address {
+        match self {
+            DeployMode::Create2 { init_code_hash } => {
+                calc_create2_addr(deployer, salt, init_code_hash)
+            }
+            DeployMode::Create3(variant) => calc_addr_with_bytes(deployer, salt, *variant),
+        }
+    }
+}
+
+impl Default for DeployMode {
+    fn default() -> Self {
+        DeployMode::Create3(Create3Variant::default())
+    }
+}
+
+impl From<Create3Variant> for DeployMode {
+    fn from(variant: Create3Variant) -> Self {
+        DeployMode::Create3(variant)
+    }
+}
+
+impl Display for DeployMode {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DeployMode::Create2 { init_code_hash } => {
+                write!(f, "create2:{}", init_code_hash)
+            }
+            DeployMode::Create3(variant) => variant.fmt(f),
+        }
+    }
+}
+
+/// Parses `create2:<hash>` with a hex encoded 32-byte init code hash, or any `Create3Variant`.
+impl FromStr for DeployMode {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let s = s.trim();
+        match s.split_once(':') {
+            Some((name, hash)) if name.eq_ignore_ascii_case("create2") => {
+                let init_code_hash = hash.parse().map_err(|e| format!("'{}': {}", hash, e))?;
+                Ok(DeployMode::Create2 { init_code_hash })
+            }
+            _ if s.eq_ignore_ascii_case("create2") => {
+                Err("mode 'create2' requires a 32-byte init code hash".to_owned())
+            }
+            _ => Ok(DeployMode::Create3(s.parse()?)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{
+        variant::{Create3Variant, DeployMode, KECCAK256_PROXY_CHILD_BYTECODE},
+        InitCodeHash,
+    };
+
+    #[test]
+    fn proxy_init_code_should_hash_to_the_preset_constant() {
+        let proxy = hex::decode("67363d3d37363d34f03d5260086018f3").unwrap();
+        assert_eq!(
+            Create3Variant::from_proxy_init_code(&proxy).proxy_init_code_hash(),
+            KECCAK256_PROXY_CHILD_BYTECODE
+        );
+    }
+
+    #[test]
+    fn should_parse_variants() {
+        let hash = InitCodeHash([7u8; 32]);
+        let runs = [
+            Create3Variant::Solady,
+            Create3Variant::Solmate,
+            Create3Variant::ZeframLou,
+            Create3Variant::Sequence,
+            Create3Variant::CreateX,
+            Create3Variant::Axelar {
+                create_deploy_hash: hash,
+            },
+            Create3Variant::Custom {
+                proxy_init_code_hash: hash,
+            },
+        ];
+        for run in runs.iter() {
+            assert_eq!(run.to_string().parse::<Create3Variant>(), Ok(*run));
+        }
+        assert!("custom".parse::<Create3Variant>().is_err());
+        assert!("custom:0x1234".parse::<Create3Variant>().is_err());
+        assert!("pancake".parse::<Create3Variant>().is_err());
+    }
+
+    #[test]
+    fn should_parse_deploy_modes() {
+        let runs = [
+            DeployMode::Create2 {
+                init_code_hash: InitCodeHash([9u8; 32]),
+            },
+            DeployMode::Create3(Create3Variant::Solady),
+            DeployMode::Create3(Create3Variant::Custom {
+                proxy_init_code_hash: InitCodeHash([3u8; 32]),
+            }),
+        ];
+        for run in runs.iter() {
+            assert_eq!(run.to_string().parse::<DeployMode>(), Ok(*run));
+        }
+        assert!("create2".parse::<DeployMode>().is_err());
+        assert!("create2:zz".parse::<DeployMode>().is_err());
+    }
+}